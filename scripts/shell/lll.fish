@@ -0,0 +1,10 @@
+# cd-on-exit wrapper for fish, save as ~/.config/fish/functions/lll.fish
+function lll
+    set tmp (mktemp)
+    command lll --choosedir $tmp $argv
+    set dir (cat $tmp)
+    rm -f $tmp
+    if test -n "$dir"; and test "$dir" != "$PWD"
+        cd -- $dir
+    end
+end
@@ -0,0 +1,11 @@
+# cd-on-exit wrapper for bash and zsh, source this from ~/.bashrc or ~/.zshrc
+lll() {
+    local tmp dir
+    tmp="$(mktemp)"
+    command lll --choosedir "$tmp" "$@"
+    dir="$(cat "$tmp")"
+    rm -f "$tmp"
+    if [ -n "$dir" ] && [ "$dir" != "$PWD" ]; then
+        cd -- "$dir" || return
+    fi
+}
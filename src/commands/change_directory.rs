@@ -29,7 +29,7 @@ impl ChangeDirectory {
         let curr_tab = &mut context.tabs[context.curr_tab_index];
 
         std::env::set_current_dir(path.as_path())?;
-        curr_tab.curr_path = path.clone();
+        curr_tab.curr_path = std::env::current_dir()?;
 
         curr_tab
             .history
@@ -44,7 +44,7 @@ impl ChangeDirectory {
             .history
             .insert(new_curr_list.file_path().clone(), new_curr_list);
 
-        curr_tab.refresh(view, &context.config_t);
+        curr_tab.refresh(view, &context.config_t, &context.usercache);
         Ok(())
     }
 }
@@ -58,7 +58,12 @@ impl std::fmt::Display for ChangeDirectory {
 }
 
 impl LllRunnable for ChangeDirectory {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
         match Self::change_directory(&self.path, context, view) {
             Ok(_) => {}
             Err(e) => return Err(LllError::IO(e)),
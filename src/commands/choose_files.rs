@@ -0,0 +1,96 @@
+use std::fs;
+use std::io::Write;
+use std::os::unix::ffi::OsStrExt;
+use std::path::PathBuf;
+
+use crate::commands::{LllCommand, LllRunnable, Quit};
+use crate::context::LllContext;
+use crate::error::LllError;
+use crate::window::LllView;
+
+use crate::ARGS;
+
+#[derive(Clone, Debug)]
+pub struct ChooseFiles;
+
+impl ChooseFiles {
+    pub fn new() -> Self {
+        ChooseFiles
+    }
+    pub const fn command() -> &'static str {
+        "choose"
+    }
+
+    // only meaningful when started as a file chooser with --choosefiles
+    pub fn is_choosing() -> bool {
+        ARGS.choosefiles.is_some()
+    }
+
+    pub fn choose(context: &mut LllContext) -> Result<(), LllError> {
+        if !Self::is_choosing() {
+            let err = std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "not running as a file chooser, start with --choosefiles",
+            );
+            return Err(LllError::IO(err));
+        }
+
+        let paths: Vec<PathBuf> = context.tabs[context.curr_tab_index]
+            .curr_list
+            .get_selected_paths()
+            .into_iter()
+            .cloned()
+            .collect();
+        if paths.is_empty() {
+            let err = std::io::Error::new(std::io::ErrorKind::NotFound, "No files selected");
+            return Err(LllError::IO(err));
+        }
+
+        Quit::quit(context)?;
+        context.chosen_files = Some(paths);
+        Ok(())
+    }
+
+    // called once ncurses has given the terminal back, so stdout is ours again
+    pub fn write_chosen_files(paths: &[PathBuf]) -> std::io::Result<()> {
+        let file_path = match ARGS.choosefiles.as_ref() {
+            Some(s) => s,
+            None => return Ok(()),
+        };
+        let separator: &[u8] = if ARGS.null { b"\0" } else { b"\n" };
+
+        let mut contents: Vec<u8> = Vec::new();
+        for path in paths {
+            contents.extend_from_slice(path.as_os_str().as_bytes());
+            contents.extend_from_slice(separator);
+        }
+
+        if file_path.as_os_str() == "-" {
+            let stdout = std::io::stdout();
+            let mut handle = stdout.lock();
+            handle.write_all(&contents)?;
+            handle.flush()
+        } else {
+            fs::write(file_path, contents)
+        }
+    }
+}
+
+impl LllCommand for ChooseFiles {}
+
+impl std::fmt::Display for ChooseFiles {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl LllRunnable for ChooseFiles {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        _: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
+        Self::choose(context)
+    }
+}
@@ -0,0 +1,117 @@
+use crate::commands::{LllCommand, LllRunnable, ShellExec};
+use crate::context::LllContext;
+use crate::error::LllError;
+use crate::window::LllView;
+
+#[derive(Clone, Debug)]
+pub struct CommandChain {
+    commands: Vec<Box<dyn LllCommand>>,
+}
+
+impl CommandChain {
+    pub fn new(commands: Vec<Box<dyn LllCommand>>) -> Self {
+        CommandChain { commands }
+    }
+
+    // splits a console line on ;, leaving quoted and escaped ones alone.
+    // shell commands take the rest of the line for themselves
+    pub fn split(line: &str) -> Vec<&str> {
+        let mut commands = Vec::new();
+        let mut start = 0;
+        let mut quote: Option<char> = None;
+        let mut escaped = false;
+
+        for (i, ch) in line.char_indices() {
+            if escaped {
+                escaped = false;
+                continue;
+            }
+            match (ch, quote) {
+                ('\\', Some('\'')) => {}
+                ('\\', _) => escaped = true,
+                ('\'', None) | ('"', None) => quote = Some(ch),
+                (c, Some(q)) if c == q => quote = None,
+                (';', None) => {
+                    let command = &line[start..i];
+                    if is_shell_command(command) {
+                        break;
+                    }
+                    commands.push(command);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        commands.push(&line[start..]);
+        commands.retain(|s| !s.trim().is_empty());
+        commands
+    }
+}
+
+fn is_shell_command(command: &str) -> bool {
+    let command = command.trim_start();
+    command.starts_with('!')
+        || command == ShellExec::command()
+        || command.starts_with(&format!("{} ", ShellExec::command()))
+}
+
+impl LllCommand for CommandChain {
+    fn repeatable(&self) -> bool {
+        self.commands.iter().any(|command| command.repeatable())
+    }
+}
+
+impl std::fmt::Display for CommandChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let commands: Vec<String> = self.commands.iter().map(|c| c.to_string()).collect();
+        f.write_str(&commands.join("; "))
+    }
+}
+
+impl LllRunnable for CommandChain {
+    // stops at the first command that fails, a count goes to every command
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        count: Option<usize>,
+    ) -> Result<(), LllError> {
+        for command in self.commands.iter() {
+            command.execute(context, view, count)?;
+            if context.exit {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_commands() {
+        assert_eq!(
+            CommandChain::split("select_files --all; copy_files"),
+            vec!["select_files --all", " copy_files"]
+        );
+        assert_eq!(CommandChain::split("cd ~;"), vec!["cd ~"]);
+    }
+
+    #[test]
+    fn split_keeps_quoted() {
+        assert_eq!(
+            CommandChain::split(r#"mkdir 'a;b' "c;d" e\;f; cd a"#),
+            vec![r#"mkdir 'a;b' "c;d" e\;f"#, " cd a"]
+        );
+    }
+
+    #[test]
+    fn split_stops_at_shell() {
+        assert_eq!(
+            CommandChain::split("cd /tmp; !make; make install"),
+            vec!["cd /tmp", " !make; make install"]
+        );
+    }
+}
@@ -1,6 +1,7 @@
-use crate::commands::{self, LllCommand, LllRunnable};
+use crate::commands::{self, LllCommand, LllRunnable, RenameFile, Search};
 use crate::context::LllContext;
 use crate::error::LllError;
+use crate::prompt_history::PromptKind;
 use crate::textfield::LllTextField;
 use crate::ui;
 use crate::window::LllView;
@@ -19,6 +20,18 @@ impl CommandLine {
         "console"
     }
 
+    // searches and renames get a history of their own
+    fn prompt_kind(&self) -> PromptKind {
+        let command = self.prefix.split_whitespace().next();
+        if command == Some(Search::command()) {
+            PromptKind::Search
+        } else if command == Some(RenameFile::command()) {
+            PromptKind::Rename
+        } else {
+            PromptKind::Console
+        }
+    }
+
     pub fn readline(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
         const PROMPT: &str = ":";
         let (term_rows, term_cols) = ui::getmaxyx();
@@ -30,31 +43,21 @@ impl CommandLine {
                 PROMPT,
                 &self.prefix,
                 &self.suffix,
+                self.prompt_kind(),
             );
             textfield.readline()
         };
 
         if let Some(s) = user_input {
-            let trimmed = s.trim_start();
-            match trimmed.find(' ') {
-                Some(ind) => {
-                    let (command, xs) = trimmed.split_at(ind);
-                    let xs = xs.trim_start();
-                    let wexp = wordexp::wordexp(xs, wordexp::Wordexp::new(0), 0);
-                    let args: Vec<&str> = match wexp.as_ref() {
-                        Ok(wexp) => wexp.iter().collect(),
-                        Err(_) => Vec::new(),
-                    };
-                    match commands::from_args(command, &args) {
-                        Ok(s) => s.execute(context, view),
-                        Err(e) => Err(LllError::Keymap(e)),
-                    }
-                }
-                None => match commands::from_args(trimmed, &Vec::new()) {
-                    Ok(s) => s.execute(context, view),
-                    Err(e) => Err(LllError::Keymap(e)),
-                },
+            let command = match commands::parse_command(&s) {
+                Ok(s) => s,
+                Err(e) => return Err(LllError::Keymap(e)),
+            };
+            command.execute(context, view, None)?;
+            if command.repeatable() {
+                context.last_command = Some((command, None));
             }
+            Ok(())
         } else {
             Ok(())
         }
@@ -70,7 +73,12 @@ impl std::fmt::Display for CommandLine {
 }
 
 impl LllRunnable for CommandLine {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
         let res = self.readline(context, view);
         ncurses::doupdate();
         res
@@ -17,9 +17,9 @@ pub fn cursor_move(mut new_index: usize, context: &mut LllContext, view: &LllVie
         }
     }
 
-    curr_tab.refresh_curr(&view.mid_win, &context.config_t);
+    curr_tab.refresh_curr(&view.mid_win, &context.config_t, &context.usercache);
     curr_tab.refresh_path_status(&view.top_win);
-    curr_tab.refresh_file_status(&view.bot_win);
+    curr_tab.refresh_file_status(&view.bot_win, &context.config_t, &context.usercache);
     ncurses::doupdate();
 }
 
@@ -46,10 +46,16 @@ impl std::fmt::Display for CursorMoveDown {
 }
 
 impl LllRunnable for CursorMoveDown {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        count: Option<usize>,
+    ) -> Result<(), LllError> {
         let movement: Option<usize> = {
+            let movement = self.movement.saturating_mul(count.unwrap_or(1));
             let curr_list = &mut context.curr_tab_mut().curr_list;
-            curr_list.index.map(|idx| idx + self.movement)
+            curr_list.index.map(|idx| idx.saturating_add(movement))
         };
         if let Some(s) = movement {
             cursor_move(s, context, view)
@@ -81,14 +87,18 @@ impl std::fmt::Display for CursorMoveUp {
 }
 
 impl LllRunnable for CursorMoveUp {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
-        let movement: Option<usize> = context.curr_tab_mut().curr_list.index.map(|idx| {
-            if idx > self.movement {
-                idx - self.movement
-            } else {
-                0
-            }
-        });
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        count: Option<usize>,
+    ) -> Result<(), LllError> {
+        let movement = self.movement.saturating_mul(count.unwrap_or(1));
+        let movement: Option<usize> = context
+            .curr_tab_mut()
+            .curr_list
+            .index
+            .map(|idx| idx.saturating_sub(movement));
         if let Some(s) = movement {
             cursor_move(s, context, view);
         }
@@ -117,13 +127,16 @@ impl std::fmt::Display for CursorMovePageUp {
 }
 
 impl LllRunnable for CursorMovePageUp {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        count: Option<usize>,
+    ) -> Result<(), LllError> {
         let movement: Option<usize> = {
             let curr_list = &mut context.curr_tab_mut().curr_list;
-            let half_page = view.mid_win.cols as usize / 2;
-            curr_list
-                .index
-                .map(|x| if x > half_page { x - half_page } else { 0 })
+            let half_page = (view.mid_win.cols as usize / 2).saturating_mul(count.unwrap_or(1));
+            curr_list.index.map(|x| x.saturating_sub(half_page))
         };
         if let Some(s) = movement {
             cursor_move(s, context, view);
@@ -153,18 +166,19 @@ impl std::fmt::Display for CursorMovePageDown {
 }
 
 impl LllRunnable for CursorMovePageDown {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        count: Option<usize>,
+    ) -> Result<(), LllError> {
         let movement: Option<usize> = {
             let curr_list = &mut context.curr_tab_mut().curr_list;
             let dir_len = curr_list.contents.len();
-            let half_page = view.mid_win.cols as usize / 2;
-            curr_list.index.map(|x| {
-                if x + half_page > dir_len - 1 {
-                    dir_len - 1
-                } else {
-                    x + half_page
-                }
-            })
+            let half_page = (view.mid_win.cols as usize / 2).saturating_mul(count.unwrap_or(1));
+            curr_list
+                .index
+                .map(|x| x.saturating_add(half_page).min(dir_len - 1))
         };
 
         if let Some(s) = movement {
@@ -195,13 +209,19 @@ impl std::fmt::Display for CursorMoveHome {
 }
 
 impl LllRunnable for CursorMoveHome {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        count: Option<usize>,
+    ) -> Result<(), LllError> {
         let movement: Option<usize> = {
             let len = context.curr_tab_mut().curr_list.contents.len();
             if len == 0 {
                 None
             } else {
-                Some(0)
+                // with a count, jumps to that entry instead
+                Some(count.map_or(0, |n| n.max(1) - 1))
             }
         };
 
@@ -233,13 +253,19 @@ impl std::fmt::Display for CursorMoveEnd {
 }
 
 impl LllRunnable for CursorMoveEnd {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        count: Option<usize>,
+    ) -> Result<(), LllError> {
         let movement: Option<usize> = {
             let len = context.curr_tab_mut().curr_list.contents.len();
             if len == 0 {
                 None
             } else {
-                Some(len - 1)
+                // with a count, jumps to that entry instead
+                Some(count.map_or(len - 1, |n| n.max(1) - 1))
             }
         };
 
@@ -4,6 +4,7 @@ use std::path;
 use crate::commands::{LllCommand, LllRunnable, ReloadDirList};
 use crate::context::LllContext;
 use crate::error::LllError;
+use crate::macros;
 use crate::ui;
 use crate::window::LllView;
 
@@ -39,7 +40,7 @@ impl DeleteFiles {
         ncurses::doupdate();
 
         let curr_tab = &mut context.tabs[context.curr_tab_index];
-        let mut ch = ncurses::getch();
+        let mut ch = macros::getch();
         if ch == 'y' as i32 || ch == KEYMAP_T.enter {
             let paths = curr_tab.curr_list.get_selected_paths();
             if paths.is_empty() {
@@ -51,7 +52,7 @@ impl DeleteFiles {
             if paths.len() > 1 {
                 ui::wprint_msg(&view.bot_win, "Are you sure? (y/N)");
                 ncurses::doupdate();
-                ch = ncurses::getch();
+                ch = macros::getch();
             } else {
                 ch = 'y' as i32;
             }
@@ -65,7 +66,11 @@ impl DeleteFiles {
     }
 }
 
-impl LllCommand for DeleteFiles {}
+impl LllCommand for DeleteFiles {
+    fn repeatable(&self) -> bool {
+        true
+    }
+}
 
 impl std::fmt::Display for DeleteFiles {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -74,10 +79,15 @@ impl std::fmt::Display for DeleteFiles {
 }
 
 impl LllRunnable for DeleteFiles {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
         let res = Self::delete_files(context, view);
         let curr_tab = &mut context.tabs[context.curr_tab_index];
-        curr_tab.refresh(view, &context.config_t);
+        curr_tab.refresh(view, &context.config_t, &context.usercache);
         ncurses::doupdate();
         match res {
             Ok(_) => Ok(()),
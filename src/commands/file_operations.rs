@@ -1,6 +1,6 @@
 use lazy_static::lazy_static;
 use std::path;
-use std::sync::{atomic, mpsc, Mutex};
+use std::sync::{mpsc, Mutex};
 use std::thread;
 use std::time;
 
@@ -13,7 +13,7 @@ use crate::window::LllView;
 lazy_static! {
     static ref SELECTED_FILES: Mutex<Option<Vec<path::PathBuf>>> = Mutex::new(None);
     static ref FILE_OPERATION: Mutex<FileOp> = Mutex::new(FileOp::Copy);
-    static ref TAB_SRC: atomic::AtomicUsize = atomic::AtomicUsize::new(0);
+    static ref TAB_SRC: Mutex<Option<usize>> = Mutex::new(None);
 }
 
 enum FileOp {
@@ -30,11 +30,27 @@ impl LocalState {
     }
 
     pub fn set_tab_src(tab_index: usize) {
-        TAB_SRC.store(tab_index, atomic::Ordering::Release);
+        let mut data = TAB_SRC.lock().unwrap();
+        *data = Some(tab_index);
     }
 
-    pub fn repopulated_selected_files(dirlist: &LllDirList) -> std::io::Result<()> {
-        let selected = dirlist.get_selected_paths();
+    pub fn tab_src() -> Option<usize> {
+        *TAB_SRC.lock().unwrap()
+    }
+
+    // with a count, takes that many entries starting at the cursor instead
+    pub fn repopulated_selected_files(
+        dirlist: &LllDirList,
+        count: Option<usize>,
+    ) -> std::io::Result<()> {
+        let selected = match (count, dirlist.index) {
+            (Some(count), Some(index)) => dirlist.contents[index..]
+                .iter()
+                .take(count)
+                .map(|entry| entry.file_path())
+                .collect(),
+            _ => dirlist.get_selected_paths(),
+        };
         if selected.is_empty() {
             Err(std::io::Error::new(
                 std::io::ErrorKind::Other,
@@ -50,9 +66,38 @@ impl LocalState {
     }
 }
 
+// tabs remembered by the clipboard and by running operations follow their
+// tabs around as other tabs are opened and closed
+pub fn tab_inserted(context: &mut LllContext, index: usize) {
+    let shift = |tab: Option<usize>| match tab {
+        Some(i) if i >= index => Some(i + 1),
+        s => s,
+    };
+    let mut data = TAB_SRC.lock().unwrap();
+    *data = shift(*data);
+    for thread in context.threads.iter_mut() {
+        thread.tab_src = shift(thread.tab_src);
+        thread.tab_dest = shift(thread.tab_dest);
+    }
+}
+
+pub fn tab_removed(context: &mut LllContext, index: usize) {
+    let shift = |tab: Option<usize>| match tab {
+        Some(i) if i == index => None,
+        Some(i) if i > index => Some(i - 1),
+        s => s,
+    };
+    let mut data = TAB_SRC.lock().unwrap();
+    *data = shift(*data);
+    for thread in context.threads.iter_mut() {
+        thread.tab_src = shift(thread.tab_src);
+        thread.tab_dest = shift(thread.tab_dest);
+    }
+}
+
 pub struct FileOperationThread<T, Q> {
-    pub tab_src: usize,
-    pub tab_dest: usize,
+    pub tab_src: Option<usize>,
+    pub tab_dest: Option<usize>,
     pub handle: thread::JoinHandle<std::io::Result<T>>,
     pub recv: mpsc::Receiver<Q>,
 }
@@ -78,7 +123,11 @@ impl CutFiles {
     }
 }
 
-impl LllCommand for CutFiles {}
+impl LllCommand for CutFiles {
+    fn repeatable(&self) -> bool {
+        true
+    }
+}
 
 impl std::fmt::Display for CutFiles {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -87,9 +136,14 @@ impl std::fmt::Display for CutFiles {
 }
 
 impl LllRunnable for CutFiles {
-    fn execute(&self, context: &mut LllContext, _: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        _: &LllView,
+        count: Option<usize>,
+    ) -> Result<(), LllError> {
         let curr_tab = context.curr_tab_ref();
-        match LocalState::repopulated_selected_files(&curr_tab.curr_list) {
+        match LocalState::repopulated_selected_files(&curr_tab.curr_list, count) {
             Ok(_) => {
                 LocalState::set_file_op(FileOp::Cut);
                 LocalState::set_tab_src(context.curr_tab_index);
@@ -112,7 +166,11 @@ impl CopyFiles {
     }
 }
 
-impl LllCommand for CopyFiles {}
+impl LllCommand for CopyFiles {
+    fn repeatable(&self) -> bool {
+        true
+    }
+}
 
 impl std::fmt::Display for CopyFiles {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -121,9 +179,14 @@ impl std::fmt::Display for CopyFiles {
 }
 
 impl LllRunnable for CopyFiles {
-    fn execute(&self, context: &mut LllContext, _: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        _: &LllView,
+        count: Option<usize>,
+    ) -> Result<(), LllError> {
         let curr_tab = context.curr_tab_ref();
-        match LocalState::repopulated_selected_files(&curr_tab.curr_list) {
+        match LocalState::repopulated_selected_files(&curr_tab.curr_list, count) {
             Ok(_) => {
                 LocalState::set_file_op(FileOp::Copy);
                 LocalState::set_tab_src(context.curr_tab_index);
@@ -138,7 +201,11 @@ pub struct PasteFiles {
     options: fs_extra::dir::CopyOptions,
 }
 
-impl LllCommand for PasteFiles {}
+impl LllCommand for PasteFiles {
+    fn repeatable(&self) -> bool {
+        true
+    }
+}
 
 impl std::fmt::Display for PasteFiles {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -152,6 +219,17 @@ impl std::fmt::Display for PasteFiles {
     }
 }
 
+// CopyOptions can't be cloned, these are the only options we ever set
+impl Clone for PasteFiles {
+    fn clone(&self) -> Self {
+        let mut options = fs_extra::dir::CopyOptions::new();
+        options.overwrite = self.options.overwrite;
+        options.skip_exist = self.options.skip_exist;
+        options.buffer_size = self.options.buffer_size;
+        PasteFiles { options }
+    }
+}
+
 impl std::fmt::Debug for PasteFiles {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         f.write_str(Self::command())
@@ -159,7 +237,12 @@ impl std::fmt::Debug for PasteFiles {
 }
 
 impl LllRunnable for PasteFiles {
-    fn execute(&self, context: &mut LllContext, _: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        _: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
         let file_operation = FILE_OPERATION.lock().unwrap();
 
         let thread = match *file_operation {
@@ -189,7 +272,7 @@ impl PasteFiles {
         &self,
         context: &mut LllContext,
     ) -> std::io::Result<FileOperationThread<u64, fs_extra::TransitProcess>> {
-        let tab_src = TAB_SRC.load(atomic::Ordering::SeqCst);
+        let tab_src = LocalState::tab_src();
         let tab_dest = context.curr_tab_index;
         let destination = context.tabs[tab_dest].curr_path.clone();
 
@@ -222,7 +305,7 @@ impl PasteFiles {
 
                 let thread = FileOperationThread {
                     tab_src,
-                    tab_dest,
+                    tab_dest: Some(tab_dest),
                     handle,
                     recv: rx,
                 };
@@ -242,7 +325,7 @@ impl PasteFiles {
         let tab_dest = context.curr_tab_index;
         let destination = context.tabs[tab_dest].curr_path.clone();
 
-        let tab_src = TAB_SRC.load(atomic::Ordering::SeqCst);
+        let tab_src = LocalState::tab_src();
         let options = self.options.clone();
 
         let (tx, rx) = mpsc::channel();
@@ -272,7 +355,7 @@ impl PasteFiles {
 
                 let thread = FileOperationThread {
                     tab_src,
-                    tab_dest,
+                    tab_dest: Some(tab_dest),
                     handle,
                     recv: rx,
                 };
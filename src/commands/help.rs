@@ -0,0 +1,283 @@
+use crate::commands::{self, CommandKeybind, LllCommand, LllRunnable};
+use crate::config::{self, LllCommandMapping};
+use crate::context::LllContext;
+use crate::error::LllError;
+use crate::macros;
+use crate::prompt_history::PromptKind;
+use crate::textfield::LllTextField;
+use crate::ui;
+use crate::window::{LllPanel, LllView};
+
+use crate::USERCOMMANDS_T;
+
+// key names as vim writes them, printable keys stand for themselves
+pub fn key_name(key: i32) -> String {
+    let name = match key {
+        ncurses::KEY_UP => "Up",
+        ncurses::KEY_DOWN => "Down",
+        ncurses::KEY_LEFT => "Left",
+        ncurses::KEY_RIGHT => "Right",
+        ncurses::KEY_HOME => "Home",
+        ncurses::KEY_END => "End",
+        ncurses::KEY_PPAGE => "PageUp",
+        ncurses::KEY_NPAGE => "PageDown",
+        ncurses::KEY_IC => "Insert",
+        ncurses::KEY_DC => "Del",
+        ncurses::KEY_BACKSPACE | 0x7F => "BS",
+        ncurses::KEY_BTAB => "S-Tab",
+        0x09 => "Tab",
+        0x0A => "Enter",
+        config::keymap::ESCAPE => "Esc",
+        0x20 => "Space",
+        0x01..=0x1A => return format!("<C-{}>", (b'a' + key as u8 - 1) as char),
+        k if k > ncurses::KEY_F0 && k <= ncurses::KEY_F0 + 64 => {
+            return format!("<F{}>", k - ncurses::KEY_F0)
+        }
+        k => {
+            return match std::char::from_u32(k as u32) {
+                Some(c) if !c.is_control() => c.to_string(),
+                _ => format!("<{}>", k),
+            }
+        }
+    };
+    format!("<{}>", name)
+}
+
+// every key sequence in the keymap and the command it runs, sorted by keys
+pub fn keybindings(keymap: &LllCommandMapping) -> Vec<(String, String)> {
+    fn flatten(keymap: &LllCommandMapping, prefix: &str, bindings: &mut Vec<(String, String)>) {
+        for (key, keybind) in keymap.iter() {
+            let keys = format!("{}{}", prefix, key_name(*key));
+            match keybind {
+                CommandKeybind::SimpleKeybind(s) => bindings.push((keys, s.to_string())),
+                CommandKeybind::CompositeKeybind(m) => flatten(m, &keys, bindings),
+            }
+        }
+    }
+
+    let mut bindings = Vec::new();
+    flatten(keymap, "", &mut bindings);
+    bindings.sort();
+    bindings
+}
+
+fn describe(command: &str) -> &'static str {
+    // commands show their arguments too, and the console a colon after its name
+    let name = command
+        .split_whitespace()
+        .next()
+        .unwrap_or("")
+        .trim_end_matches(':');
+    match commands::COMMANDS.iter().find(|(s, _)| *s == name) {
+        Some((_, description)) => description,
+        None if USERCOMMANDS_T.contains_key(name) => "user command",
+        None => "",
+    }
+}
+
+// longer lines would wrap onto the row below
+fn truncate(line: &str, cols: usize) -> &str {
+    let mut width = 0;
+    for (i, ch) in line.char_indices() {
+        width += unicode_width::UnicodeWidthChar::width(ch).unwrap_or(0);
+        if width > cols {
+            return &line[..i];
+        }
+    }
+    line
+}
+
+fn help_lines(bindings: &[(String, String)]) -> Vec<String> {
+    let mut lines = vec![String::from("Key bindings")];
+    for (keys, command) in bindings.iter() {
+        lines.push(format!(
+            "  {:<12}{:<32}{}",
+            keys,
+            command,
+            describe(command)
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(String::from("Commands"));
+    for (name, description) in commands::COMMANDS.iter() {
+        lines.push(format!("  {:<28}{}", name, description));
+    }
+
+    if !USERCOMMANDS_T.is_empty() {
+        lines.push(String::new());
+        lines.push(String::from("User commands"));
+        let mut usercommands: Vec<_> = USERCOMMANDS_T.values().collect();
+        usercommands.sort_by(|a, b| a.name.cmp(&b.name));
+        for command in usercommands {
+            let body = match command.shell.as_ref() {
+                Some(s) => format!("!{}", s),
+                None => command.commands.join("; "),
+            };
+            lines.push(format!("  {:<28}{}", command.name, body));
+        }
+    }
+    lines
+}
+
+#[derive(Clone, Debug)]
+pub struct Help;
+
+impl Help {
+    pub fn new() -> Self {
+        Help
+    }
+    pub const fn command() -> &'static str {
+        "help"
+    }
+
+    // j/k and the arrow keys scroll, / filters lines down to those
+    // containing some text, escape clears the filter, q closes
+    pub fn help(context: &LllContext) {
+        const TITLE: &str = "help: j/k scroll, / search, q quit";
+
+        let all_lines = help_lines(&context.keybindings);
+        let mut lines: Vec<String> = all_lines.clone();
+        let mut query = String::new();
+        let mut offset = 0;
+
+        let (term_rows, term_cols) = ui::getmaxyx();
+        let win = LllPanel::new(term_rows, term_cols, (0, 0));
+        win.move_to_top();
+        let page_rows = (term_rows as usize).saturating_sub(1).max(1);
+        ncurses::timeout(-1);
+
+        loop {
+            let max_offset = lines.len().saturating_sub(page_rows);
+            offset = offset.min(max_offset);
+
+            ncurses::werase(win.win);
+            let end = lines.len().min(offset + page_rows);
+            for (i, line) in lines[offset..end].iter().enumerate() {
+                ncurses::mvwaddstr(win.win, i as i32 + 1, 0, truncate(line, term_cols as usize));
+            }
+            let title = if query.is_empty() {
+                String::from(TITLE)
+            } else {
+                format!("{} (filtered by \"{}\")", TITLE, query)
+            };
+            ncurses::mvwaddstr(win.win, 0, 0, truncate(&title, term_cols as usize));
+            ncurses::mvwchgat(win.win, 0, 0, -1, ncurses::A_STANDOUT(), 0);
+            win.queue_for_refresh();
+            ncurses::doupdate();
+
+            let ch = macros::getch();
+            if ch == ncurses::KEY_RESIZE {
+                // closes help, leaving the resize for the main loop
+                macros::unget(ch);
+                break;
+            } else if ch == 'q' as i32 {
+                break;
+            } else if ch == config::keymap::ESCAPE {
+                if query.is_empty() {
+                    break;
+                }
+                query.clear();
+                lines = all_lines.clone();
+            } else if ch == 'j' as i32 || ch == ncurses::KEY_DOWN {
+                offset += 1;
+            } else if ch == 'k' as i32 || ch == ncurses::KEY_UP {
+                offset = offset.saturating_sub(1);
+            } else if ch == ncurses::KEY_NPAGE || ch == ' ' as i32 {
+                offset += page_rows;
+            } else if ch == ncurses::KEY_PPAGE {
+                offset = offset.saturating_sub(page_rows);
+            } else if ch == 'g' as i32 || ch == ncurses::KEY_HOME {
+                offset = 0;
+            } else if ch == 'G' as i32 || ch == ncurses::KEY_END {
+                offset = max_offset;
+            } else if ch == '/' as i32 {
+                let textfield = LllTextField::new(
+                    1,
+                    term_cols,
+                    (term_rows as usize - 1, 0),
+                    "/",
+                    "",
+                    "",
+                    PromptKind::Help,
+                );
+                if let Some(s) = textfield.readline() {
+                    query = s.to_lowercase();
+                    lines = all_lines
+                        .iter()
+                        .filter(|line| line.to_lowercase().contains(query.as_str()))
+                        .cloned()
+                        .collect();
+                    offset = 0;
+                }
+            }
+        }
+    }
+}
+
+impl LllCommand for Help {}
+
+impl std::fmt::Display for Help {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl LllRunnable for Help {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        _: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
+        Self::help(context);
+        ncurses::doupdate();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::commands::{CursorMoveHome, Quit};
+
+    #[test]
+    fn key_names() {
+        assert_eq!(key_name('g' as i32), "g");
+        assert_eq!(key_name(' ' as i32), "<Space>");
+        assert_eq!(key_name(0x17), "<C-w>");
+        assert_eq!(key_name(ncurses::KEY_UP), "<Up>");
+        assert_eq!(key_name(ncurses::KEY_F0 + 1), "<F1>");
+    }
+
+    #[test]
+    fn truncate_to_width() {
+        assert_eq!(truncate("cursor_move_up", 6), "cursor");
+        assert_eq!(truncate("日本語", 5), "日本");
+        assert_eq!(truncate("q", 6), "q");
+    }
+
+    #[test]
+    fn flatten_keymap() {
+        let mut composite = LllCommandMapping::new();
+        composite.insert(
+            'g' as i32,
+            CommandKeybind::SimpleKeybind(Box::new(CursorMoveHome::new())),
+        );
+        let mut keymap = LllCommandMapping::new();
+        keymap.insert(
+            'q' as i32,
+            CommandKeybind::SimpleKeybind(Box::new(Quit::new())),
+        );
+        keymap.insert('g' as i32, CommandKeybind::CompositeKeybind(composite));
+
+        assert_eq!(
+            keybindings(&keymap),
+            vec![
+                (String::from("gg"), String::from("cursor_move_home")),
+                (String::from("q"), String::from("quit")),
+            ]
+        );
+    }
+}
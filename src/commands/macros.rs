@@ -0,0 +1,135 @@
+use crate::commands::{LllCommand, LllRunnable};
+use crate::config;
+use crate::context::LllContext;
+use crate::error::{KeymapError, LllError};
+use crate::macros;
+use crate::ui;
+use crate::window::LllView;
+
+// without a register given, the next key names one, as in vim's qa and @a
+fn read_register(
+    command: &'static str,
+    register: Option<&String>,
+    view: &LllView,
+) -> Result<Option<String>, LllError> {
+    if let Some(s) = register {
+        return Ok(Some(s.clone()));
+    }
+
+    ui::wprint_msg(&view.bot_win, &format!("{}: register?", command));
+    ncurses::doupdate();
+    let ch = macros::getch();
+    ncurses::werase(view.bot_win.win);
+    ncurses::wnoutrefresh(view.bot_win.win);
+    if ch == config::keymap::ESCAPE {
+        return Ok(None);
+    }
+    match std::char::from_u32(ch as u32) {
+        Some(c) if c.is_alphanumeric() => Ok(Some(c.to_string())),
+        _ => Err(LllError::Keymap(KeymapError::new(
+            Some(command),
+            format!("Invalid register: {}", ch),
+        ))),
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MacroRecord {
+    register: Option<String>,
+}
+
+impl MacroRecord {
+    pub fn new(register: Option<String>) -> Self {
+        MacroRecord { register }
+    }
+    pub const fn command() -> &'static str {
+        "macro_record"
+    }
+
+    // stops the recording if there is one, otherwise starts one
+    pub fn macro_record(&self, context: &LllContext, view: &LllView) -> Result<(), LllError> {
+        if let Some(register) = macros::stop_recording() {
+            ui::wprint_msg(&view.bot_win, &format!("Recorded @{}", register));
+            if context.config_t.save_macros {
+                if let Err(e) = macros::save() {
+                    return Err(LllError::IO(e));
+                }
+            }
+            return Ok(());
+        }
+
+        if let Some(register) = read_register(Self::command(), self.register.as_ref(), view)? {
+            ui::wprint_msg(&view.bot_win, &format!("Recording @{}", register));
+            macros::start_recording(register);
+        }
+        Ok(())
+    }
+}
+
+impl LllCommand for MacroRecord {}
+
+impl std::fmt::Display for MacroRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.register.as_ref() {
+            Some(s) => write!(f, "{} {}", Self::command(), s),
+            None => f.write_str(Self::command()),
+        }
+    }
+}
+
+impl LllRunnable for MacroRecord {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
+        let res = self.macro_record(context, view);
+        ncurses::doupdate();
+        res
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MacroPlay {
+    register: Option<String>,
+}
+
+impl MacroPlay {
+    pub fn new(register: Option<String>) -> Self {
+        MacroPlay { register }
+    }
+    pub const fn command() -> &'static str {
+        "macro_play"
+    }
+}
+
+impl LllCommand for MacroPlay {}
+
+impl std::fmt::Display for MacroPlay {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.register.as_ref() {
+            Some(s) => write!(f, "{} {}", Self::command(), s),
+            None => f.write_str(Self::command()),
+        }
+    }
+}
+
+impl LllRunnable for MacroPlay {
+    // keys are queued here and read back by the main loop
+    fn execute(
+        &self,
+        _: &mut LllContext,
+        view: &LllView,
+        count: Option<usize>,
+    ) -> Result<(), LllError> {
+        let register = match read_register(Self::command(), self.register.as_ref(), view)? {
+            Some(s) => s,
+            None => return Ok(()),
+        };
+        match macros::play(&register, count.unwrap_or(1)) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(LllError::Keymap(KeymapError::new(Some(Self::command()), e))),
+        }
+    }
+}
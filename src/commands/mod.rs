@@ -1,28 +1,43 @@
 mod change_directory;
+mod choose_files;
+mod command_chain;
 mod command_line;
 mod cursor_move;
 mod delete_files;
 mod file_operations;
+mod help;
+mod macros;
 mod new_directory;
 mod open_file;
 mod parent_directory;
 mod quit;
 mod reload_dir;
 mod rename_file;
+mod repeat_last;
 mod search;
 mod selection;
 mod set_mode;
+mod shell;
+mod shell_exec;
 mod show_hidden;
+mod tab_operations;
 mod tab_switch;
+mod user_command;
 
 pub use self::change_directory::ChangeDirectory;
+pub use self::choose_files::ChooseFiles;
+pub use self::command_chain::CommandChain;
 pub use self::command_line::CommandLine;
 pub use self::cursor_move::{
     CursorMoveDown, CursorMoveEnd, CursorMoveHome, CursorMovePageDown, CursorMovePageUp,
     CursorMoveUp,
 };
 pub use self::delete_files::DeleteFiles;
-pub use self::file_operations::{CopyFiles, CutFiles, FileOperationThread, PasteFiles};
+pub use self::file_operations::{
+    tab_inserted, tab_removed, CopyFiles, CutFiles, FileOperationThread, PasteFiles,
+};
+pub use self::help::{keybindings, Help};
+pub use self::macros::{MacroPlay, MacroRecord};
 pub use self::new_directory::NewDirectory;
 pub use self::open_file::{OpenFile, OpenFileWith};
 pub use self::parent_directory::ParentDirectory;
@@ -30,11 +45,16 @@ pub use self::quit::ForceQuit;
 pub use self::quit::Quit;
 pub use self::reload_dir::ReloadDirList;
 pub use self::rename_file::{RenameFile, RenameFileAppend, RenameFilePrepend};
+pub use self::repeat_last::RepeatLast;
 pub use self::search::{Search, SearchNext, SearchPrev};
 pub use self::selection::SelectFiles;
 pub use self::set_mode::SetMode;
+pub use self::shell::Shell;
+pub use self::shell_exec::{ShellExec, ShellJob};
 pub use self::show_hidden::ToggleHiddenFiles;
+pub use self::tab_operations::{CloseTab, DuplicateTab, NewTab};
 pub use self::tab_switch::TabSwitch;
+pub use self::user_command::UserCommand;
 
 use std::path::PathBuf;
 
@@ -43,7 +63,7 @@ use crate::context::LllContext;
 use crate::error::{KeymapError, LllError};
 use crate::window::LllView;
 
-use crate::HOME_DIR;
+use crate::{HOME_DIR, USERCOMMANDS_T};
 
 #[derive(Debug)]
 pub enum CommandKeybind {
@@ -52,10 +72,36 @@ pub enum CommandKeybind {
 }
 
 pub trait LllRunnable {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError>;
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError>;
+}
+
+pub trait LllCommand: LllRunnable + LllCommandClone + std::fmt::Display + std::fmt::Debug {
+    // commands that change files or the selection, for repeat_last
+    fn repeatable(&self) -> bool {
+        false
+    }
+}
+
+pub trait LllCommandClone {
+    fn clone_box(&self) -> Box<dyn LllCommand>;
 }
 
-pub trait LllCommand: LllRunnable + std::fmt::Display + std::fmt::Debug {}
+impl<T: 'static + LllCommand + Clone> LllCommandClone for T {
+    fn clone_box(&self) -> Box<dyn LllCommand> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn LllCommand> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
 
 impl std::fmt::Display for CommandKeybind {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -66,6 +112,69 @@ impl std::fmt::Display for CommandKeybind {
     }
 }
 
+// every command from_args knows with what it does, for completion and help
+pub const COMMANDS: &[(&str, &str)] = &[
+    ("cd", "change directory, to home without a path"),
+    (
+        "choose",
+        "pick the selected files and quit, with --choosefiles",
+    ),
+    ("copy_files", "copy the selected files, to paste later"),
+    ("console", "open the command line"),
+    ("cursor_move_down", "move the cursor down"),
+    ("cursor_move_up", "move the cursor up"),
+    ("cursor_move_home", "move the cursor to the first entry"),
+    ("cursor_move_end", "move the cursor to the last entry"),
+    ("cursor_move_page_up", "move the cursor up half a page"),
+    ("cursor_move_page_down", "move the cursor down half a page"),
+    ("cut_files", "cut the selected files, to paste later"),
+    ("delete_files", "delete the selected files"),
+    ("force_quit", "quit, even with operations running"),
+    ("help", "list key bindings and commands"),
+    ("macro_play", "play back the keys recorded in a register"),
+    ("macro_record", "record keys into a register, again to stop"),
+    ("mkdir", "create directories"),
+    ("open_file", "open the file or enter the directory"),
+    (
+        "open_file_with",
+        "open the file with a program chosen from a list",
+    ),
+    ("paste_files", "paste cut or copied files here"),
+    ("quit", "quit"),
+    ("reload_dir_list", "reload the current directory"),
+    ("rename", "rename the file under the cursor"),
+    ("rename_append", "rename, starting at the end of the name"),
+    (
+        "rename_prepend",
+        "rename, starting at the start of the name",
+    ),
+    (
+        "repeat_last",
+        "run the last command that changed something again",
+    ),
+    ("search", "jump to the next file matching a pattern"),
+    ("search_next", "jump to the next match"),
+    ("search_prev", "jump to the previous match"),
+    (
+        "select_files",
+        "select the file under the cursor, or all with --all",
+    ),
+    ("set_mode", "change the permissions of the selected files"),
+    ("shell", "open a shell in the current directory"),
+    (
+        "shell_exec",
+        "run a shell command, %s %f %d expand to paths",
+    ),
+    ("tab_close", "close the current tab"),
+    ("tab_duplicate", "open a copy of the current tab"),
+    ("tab_new", "open a tab, in home without a path"),
+    (
+        "tab_switch",
+        "move to another tab, relative to the current one",
+    ),
+    ("toggle_hidden", "show or hide hidden files"),
+];
+
 pub fn from_args(command: &str, args: &[&str]) -> Result<Box<dyn LllCommand>, KeymapError> {
     match command {
         "cd" => match args.len() {
@@ -85,6 +194,7 @@ pub fn from_args(command: &str, args: &[&str]) -> Result<Box<dyn LllCommand>, Ke
                 format!("Expected 1 argument, got {}", i),
             )),
         },
+        "choose" => Ok(Box::new(self::ChooseFiles::new())),
         "copy_files" => Ok(Box::new(self::CopyFiles::new())),
         "console" => match args.len() {
             0 => Ok(Box::new(self::CommandLine::new(
@@ -129,6 +239,25 @@ pub fn from_args(command: &str, args: &[&str]) -> Result<Box<dyn LllCommand>, Ke
         "cut_files" => Ok(Box::new(self::CutFiles::new())),
         "delete_files" => Ok(Box::new(self::DeleteFiles::new())),
         "force_quit" => Ok(Box::new(self::ForceQuit::new())),
+        "help" => Ok(Box::new(self::Help::new())),
+        "macro_play" => match args.len() {
+            0 => Ok(Box::new(self::MacroPlay::new(None))),
+            1 => Ok(Box::new(self::MacroPlay::new(Some(String::from(args[0]))))),
+            i => Err(KeymapError::new(
+                Some("macro_play"),
+                format!("Expected 0 or 1 arguments, got {}", i),
+            )),
+        },
+        "macro_record" => match args.len() {
+            0 => Ok(Box::new(self::MacroRecord::new(None))),
+            1 => Ok(Box::new(self::MacroRecord::new(Some(String::from(
+                args[0],
+            ))))),
+            i => Err(KeymapError::new(
+                Some("macro_record"),
+                format!("Expected 0 or 1 arguments, got {}", i),
+            )),
+        },
         "mkdir" => {
             if args.is_empty() {
                 Err(KeymapError::new(
@@ -173,6 +302,7 @@ pub fn from_args(command: &str, args: &[&str]) -> Result<Box<dyn LllCommand>, Ke
         },
         "rename_append" => Ok(Box::new(self::RenameFileAppend::new())),
         "rename_prepend" => Ok(Box::new(self::RenameFilePrepend::new())),
+        "repeat_last" => Ok(Box::new(self::RepeatLast::new())),
         "search" => match args.len() {
             1 => Ok(Box::new(self::Search::new(args[0]))),
             i => Err(KeymapError::new(
@@ -200,6 +330,27 @@ pub fn from_args(command: &str, args: &[&str]) -> Result<Box<dyn LllCommand>, Ke
             Ok(Box::new(self::SelectFiles::new(toggle, all)))
         }
         "set_mode" => Ok(Box::new(self::SetMode::new())),
+        "shell" => Ok(Box::new(self::Shell::new())),
+        "shell_exec" => {
+            if args.is_empty() {
+                Err(KeymapError::new(
+                    Some("shell_exec"),
+                    String::from("shell_exec requires additional parameter"),
+                ))
+            } else {
+                Ok(Box::new(self::ShellExec::parse(&args.join(" "))))
+            }
+        }
+        "tab_close" => Ok(Box::new(self::CloseTab::new())),
+        "tab_duplicate" => Ok(Box::new(self::DuplicateTab::new())),
+        "tab_new" => match args.len() {
+            0 => Ok(Box::new(self::NewTab::new(None))),
+            1 => Ok(Box::new(self::NewTab::new(Some(PathBuf::from(args[0]))))),
+            i => Err(KeymapError::new(
+                Some("tab_new"),
+                format!("Expected 0 or 1 arguments, got {}", i),
+            )),
+        },
         "tab_switch" => {
             if args.len() == 1 {
                 match args[0].parse::<i32>() {
@@ -214,6 +365,50 @@ pub fn from_args(command: &str, args: &[&str]) -> Result<Box<dyn LllCommand>, Ke
             }
         }
         "toggle_hidden" => Ok(Box::new(self::ToggleHiddenFiles::new())),
-        inp => Err(KeymapError::new(None, format!("Unknown command: {}", inp))),
+        inp => match USERCOMMANDS_T.get(inp) {
+            Some(s) => {
+                let args: Vec<String> = args.iter().map(|arg| String::from(*arg)).collect();
+                Ok(Box::new(self::UserCommand::new(s.clone(), args)))
+            }
+            None => Err(KeymapError::new(None, format!("Unknown command: {}", inp))),
+        },
+    }
+}
+
+// parses a line as typed into the console, commands can be chained with ;
+pub fn parse_command(line: &str) -> Result<Box<dyn LllCommand>, KeymapError> {
+    let lines = self::CommandChain::split(line);
+    if lines.len() <= 1 {
+        // a lone trailing ; is split off too
+        return parse_single_command(lines.first().copied().unwrap_or(line));
+    }
+    let mut commands = Vec::with_capacity(lines.len());
+    for line in lines {
+        commands.push(parse_single_command(line)?);
+    }
+    Ok(Box::new(self::CommandChain::new(commands)))
+}
+
+fn parse_single_command(line: &str) -> Result<Box<dyn LllCommand>, KeymapError> {
+    let trimmed = line.trim_start();
+    // shell commands are passed on as typed, without word expansion
+    if trimmed.starts_with('!') {
+        return Ok(Box::new(self::ShellExec::parse(&trimmed[1..])));
+    }
+    match trimmed.find(' ') {
+        Some(ind) => {
+            let (command, xs) = trimmed.split_at(ind);
+            let xs = xs.trim_start();
+            if command == self::ShellExec::command() {
+                return Ok(Box::new(self::ShellExec::parse(xs)));
+            }
+            let wexp = wordexp::wordexp(xs, wordexp::Wordexp::new(0), 0);
+            let args: Vec<&str> = match wexp.as_ref() {
+                Ok(wexp) => wexp.iter().collect(),
+                Err(_) => Vec::new(),
+            };
+            from_args(command, &args)
+        }
+        None => from_args(trimmed, &Vec::new()),
     }
 }
@@ -19,7 +19,11 @@ impl NewDirectory {
     }
 }
 
-impl LllCommand for NewDirectory {}
+impl LllCommand for NewDirectory {
+    fn repeatable(&self) -> bool {
+        true
+    }
+}
 
 impl std::fmt::Display for NewDirectory {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -28,7 +32,12 @@ impl std::fmt::Display for NewDirectory {
 }
 
 impl LllRunnable for NewDirectory {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
         for path in &self.paths {
             match std::fs::create_dir_all(path) {
                 Ok(_) => {}
@@ -39,7 +48,7 @@ impl LllRunnable for NewDirectory {
         match res {
             Ok(_) => {
                 let curr_tab = &mut context.tabs[context.curr_tab_index];
-                curr_tab.refresh(view, &context.config_t);
+                curr_tab.refresh(view, &context.config_t, &context.usercache);
                 ncurses::doupdate();
             }
             Err(e) => return Err(LllError::IO(e)),
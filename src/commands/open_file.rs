@@ -1,10 +1,11 @@
 use std::path::{Path, PathBuf};
 
-use crate::commands::{LllCommand, LllRunnable};
+use crate::commands::{ChooseFiles, LllCommand, LllRunnable};
 use crate::config::mimetype;
 use crate::context::LllContext;
 use crate::error::LllError;
 use crate::history::DirectoryHistory;
+use crate::prompt_history::PromptKind;
 use crate::textfield::LllTextField;
 use crate::ui;
 use crate::unix;
@@ -55,7 +56,7 @@ impl OpenFile {
                     .curr_list
                     .update_contents(&context.config_t.sort_option)?;
             }
-            curr_tab.refresh(view, &context.config_t);
+            curr_tab.refresh(view, &context.config_t, &context.usercache);
         } else {
             let curr_tab = &context.tabs[context.curr_tab_index];
             let paths = curr_tab.curr_list.get_selected_paths();
@@ -71,7 +72,7 @@ impl OpenFile {
                     .curr_list
                     .update_contents(&context.config_t.sort_option)?;
             }
-            curr_tab.refresh(view, &context.config_t);
+            curr_tab.refresh(view, &context.config_t, &context.usercache);
         }
         ncurses::doupdate();
         Ok(())
@@ -119,7 +120,21 @@ impl std::fmt::Display for OpenFile {
 }
 
 impl LllRunnable for OpenFile {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
+        // as a file chooser, opening a file picks it instead
+        if ChooseFiles::is_choosing() {
+            let curr_list = &context.tabs[context.curr_tab_index].curr_list;
+            if let Some(entry) = curr_list.get_curr_ref() {
+                if !entry.file_path().is_dir() {
+                    return ChooseFiles::choose(context);
+                }
+            }
+        }
         match Self::open(context, view) {
             Ok(_) => Ok(()),
             Err(e) => Err(LllError::IO(e)),
@@ -163,8 +178,15 @@ impl OpenFileWith {
             ui::display_menu(&display_win, &display_vec);
             ncurses::doupdate();
 
-            let textfield =
-                LllTextField::new(1, term_cols, (term_rows as usize - 1, 0), PROMPT, "", "");
+            let textfield = LllTextField::new(
+                1,
+                term_cols,
+                (term_rows as usize - 1, 0),
+                PROMPT,
+                "",
+                "",
+                PromptKind::OpenWith,
+            );
             user_input = textfield.readline();
         }
         ncurses::doupdate();
@@ -206,7 +228,12 @@ impl std::fmt::Display for OpenFileWith {
 }
 
 impl LllRunnable for OpenFileWith {
-    fn execute(&self, context: &mut LllContext, _: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        _: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
         let curr_list = &context.tabs[context.curr_tab_index].curr_list;
         let paths = curr_list.get_selected_paths();
         Self::open_with(&paths);
@@ -34,7 +34,7 @@ impl ParentDirectory {
             .history
             .insert(new_curr_list.file_path().clone(), new_curr_list);
 
-        curr_tab.refresh(view, &context.config_t);
+        curr_tab.refresh(view, &context.config_t, &context.usercache);
         ncurses::doupdate();
         Ok(())
     }
@@ -49,7 +49,12 @@ impl std::fmt::Display for ParentDirectory {
 }
 
 impl LllRunnable for ParentDirectory {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
         match Self::parent_directory(context, view) {
             Ok(_) => Ok(()),
             Err(e) => Err(LllError::IO(e)),
@@ -1,8 +1,22 @@
+use std::fs;
+use std::os::unix::ffi::OsStrExt;
+
 use crate::commands::{LllCommand, LllRunnable};
 use crate::context::LllContext;
 use crate::error::LllError;
 use crate::window::LllView;
 
+use crate::ARGS;
+
+// lets a wrapping shell follow us into the directory we quit in
+fn write_choosedir(context: &LllContext) -> std::io::Result<()> {
+    if let Some(file_path) = ARGS.choosedir.as_ref() {
+        let curr_path = &context.curr_tab_ref().curr_path;
+        fs::write(file_path, curr_path.as_os_str().as_bytes())?;
+    }
+    Ok(())
+}
+
 #[derive(Clone, Debug)]
 pub struct Quit;
 
@@ -22,8 +36,13 @@ impl Quit {
             );
             Err(LllError::IO(err))
         } else {
-            context.exit = true;
-            Ok(())
+            match write_choosedir(context) {
+                Ok(_) => {
+                    context.exit = true;
+                    Ok(())
+                }
+                Err(e) => Err(LllError::IO(e)),
+            }
         }
     }
 }
@@ -37,7 +56,12 @@ impl std::fmt::Display for Quit {
 }
 
 impl LllRunnable for Quit {
-    fn execute(&self, context: &mut LllContext, _: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        _: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
         Self::quit(context)
     }
 }
@@ -60,6 +84,8 @@ impl ForceQuit {
     }
 
     pub fn force_quit(context: &mut LllContext) {
+        // nothing can be done about a failed write when quitting regardless
+        let _ = write_choosedir(context);
         context.exit = true;
     }
 }
@@ -73,7 +99,12 @@ impl std::fmt::Display for ForceQuit {
 }
 
 impl LllRunnable for ForceQuit {
-    fn execute(&self, context: &mut LllContext, _: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        _: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
         Self::force_quit(context);
         Ok(())
     }
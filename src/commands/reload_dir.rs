@@ -47,11 +47,16 @@ impl std::fmt::Display for ReloadDirList {
 }
 
 impl LllRunnable for ReloadDirList {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
         match Self::reload(context.curr_tab_index, context) {
             Ok(_) => {
                 let curr_tab = &mut context.tabs[context.curr_tab_index];
-                curr_tab.refresh(view, &context.config_t);
+                curr_tab.refresh(view, &context.config_t, &context.usercache);
                 ncurses::doupdate();
                 Ok(())
             }
@@ -45,12 +45,16 @@ impl RenameFile {
         curr_tab
             .curr_list
             .update_contents(&context.config_t.sort_option)?;
-        curr_tab.refresh_curr(&view.mid_win, &context.config_t);
+        curr_tab.refresh_curr(&view.mid_win, &context.config_t, &context.usercache);
         Ok(())
     }
 }
 
-impl LllCommand for RenameFile {}
+impl LllCommand for RenameFile {
+    fn repeatable(&self) -> bool {
+        true
+    }
+}
 
 impl std::fmt::Display for RenameFile {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -59,7 +63,12 @@ impl std::fmt::Display for RenameFile {
 }
 
 impl LllRunnable for RenameFile {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
         let mut path: Option<path::PathBuf> = None;
 
         let curr_list = &context.tabs[context.curr_tab_index].curr_list;
@@ -119,7 +128,12 @@ impl std::fmt::Display for RenameFileAppend {
 }
 
 impl LllRunnable for RenameFileAppend {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
         let curr_list = &context.tabs[context.curr_tab_index].curr_list;
         let file_name = match curr_list.get_curr_ref() {
             Some(s) => {
@@ -176,7 +190,12 @@ impl std::fmt::Display for RenameFilePrepend {
 }
 
 impl LllRunnable for RenameFilePrepend {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
         let curr_list = &context.tabs[context.curr_tab_index].curr_list;
         let file_name = match curr_list.get_curr_ref() {
             Some(s) => {
@@ -0,0 +1,48 @@
+use crate::commands::{LllCommand, LllRunnable};
+use crate::context::LllContext;
+use crate::error::{KeymapError, LllError};
+use crate::window::LllView;
+
+#[derive(Clone, Debug)]
+pub struct RepeatLast;
+
+impl RepeatLast {
+    pub fn new() -> Self {
+        RepeatLast
+    }
+    pub const fn command() -> &'static str {
+        "repeat_last"
+    }
+}
+
+impl LllCommand for RepeatLast {}
+
+impl std::fmt::Display for RepeatLast {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl LllRunnable for RepeatLast {
+    // a new count replaces the one the command was first run with
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        count: Option<usize>,
+    ) -> Result<(), LllError> {
+        let (command, last_count) = match context.last_command.take() {
+            Some(s) => s,
+            None => {
+                return Err(LllError::Keymap(KeymapError::new(
+                    Some(Self::command()),
+                    String::from("No command to repeat"),
+                )));
+            }
+        };
+        let count = count.or(last_count);
+        let res = command.execute(context, view, count);
+        context.last_command = Some((command, count));
+        res
+    }
+}
@@ -73,7 +73,12 @@ impl std::fmt::Display for Search {
 }
 
 impl LllRunnable for Search {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
         let index = Self::search(&context.tabs[context.curr_tab_index], &self.pattern);
         if let Some(index) = index {
             cursor_move::cursor_move(index, context, view);
@@ -128,8 +133,15 @@ impl std::fmt::Display for SearchNext {
 }
 
 impl LllRunnable for SearchNext {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
-        search_with_func(context, view, Search::search);
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        count: Option<usize>,
+    ) -> Result<(), LllError> {
+        for _ in 0..count.unwrap_or(1) {
+            search_with_func(context, view, Search::search);
+        }
         Ok(())
     }
 }
@@ -155,8 +167,15 @@ impl std::fmt::Display for SearchPrev {
 }
 
 impl LllRunnable for SearchPrev {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
-        search_with_func(context, view, Search::search_rev);
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        count: Option<usize>,
+    ) -> Result<(), LllError> {
+        for _ in 0..count.unwrap_or(1) {
+            search_with_func(context, view, Search::search_rev);
+        }
         Ok(())
     }
 }
@@ -18,7 +18,11 @@ impl SelectFiles {
     }
 }
 
-impl LllCommand for SelectFiles {}
+impl LllCommand for SelectFiles {
+    fn repeatable(&self) -> bool {
+        true
+    }
+}
 
 impl std::fmt::Display for SelectFiles {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -34,35 +38,53 @@ impl std::fmt::Display for SelectFiles {
 }
 
 impl LllRunnable for SelectFiles {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        count: Option<usize>,
+    ) -> Result<(), LllError> {
         let curr_tab = &mut context.tabs[context.curr_tab_index];
         if self.toggle {
             if !self.all {
-                let curr_list = &mut curr_tab.curr_list;
-                if let Some(s) = curr_list.get_curr_mut() {
-                    s.set_selected(!s.is_selected());
-                    CursorMoveDown::new(1).execute(context, view)?;
+                for _ in 0..count.unwrap_or(1) {
+                    let curr_list = &mut context.tabs[context.curr_tab_index].curr_list;
+                    let index = curr_list.index;
+                    if let Some(s) = curr_list.get_curr_mut() {
+                        s.set_selected(!s.is_selected());
+                        CursorMoveDown::new(1).execute(context, view, None)?;
+                    }
+                    // the cursor stops at the last entry, which mustn't be toggled again
+                    if context.tabs[context.curr_tab_index].curr_list.index == index {
+                        break;
+                    }
                 }
             } else {
                 let curr_list = &mut curr_tab.curr_list;
                 for curr in &mut curr_list.contents {
                     curr.set_selected(!curr.is_selected());
                 }
-                curr_tab.refresh_curr(&view.mid_win, &context.config_t);
+                curr_tab.refresh_curr(&view.mid_win, &context.config_t, &context.usercache);
                 ncurses::doupdate();
             }
         } else if !self.all {
-            let curr_list = &mut curr_tab.curr_list;
-            if let Some(s) = curr_list.get_curr_mut() {
-                s.set_selected(true);
-                CursorMoveDown::new(1).execute(context, view)?;
+            for _ in 0..count.unwrap_or(1) {
+                let curr_list = &mut context.tabs[context.curr_tab_index].curr_list;
+                let index = curr_list.index;
+                if let Some(s) = curr_list.get_curr_mut() {
+                    s.set_selected(true);
+                    CursorMoveDown::new(1).execute(context, view, None)?;
+                }
+                if context.tabs[context.curr_tab_index].curr_list.index == index {
+                    break;
+                }
             }
         } else {
             let curr_list = &mut curr_tab.curr_list;
             for curr in &mut curr_list.contents {
                 curr.set_selected(true);
             }
-            curr_tab.refresh_curr(&view.mid_win, &context.config_t);
+            curr_tab.refresh_curr(&view.mid_win, &context.config_t, &context.usercache);
             ncurses::doupdate();
         }
         Ok(())
@@ -2,6 +2,7 @@ use crate::commands::{CursorMoveDown, LllCommand, LllRunnable};
 use crate::context::LllContext;
 use crate::error::LllError;
 use crate::fs::LllDirEntry;
+use crate::prompt_history::PromptKind;
 use crate::textfield::LllTextField;
 use crate::ui;
 use crate::unix;
@@ -44,6 +45,7 @@ impl SetMode {
                 PROMPT,
                 &initial,
                 "",
+                PromptKind::SetMode,
             );
             textfield.readline()
         };
@@ -67,7 +69,11 @@ impl SetMode {
     }
 }
 
-impl LllCommand for SetMode {}
+impl LllCommand for SetMode {
+    fn repeatable(&self) -> bool {
+        true
+    }
+}
 
 impl std::fmt::Display for SetMode {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -76,7 +82,12 @@ impl std::fmt::Display for SetMode {
 }
 
 impl LllRunnable for SetMode {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
         use std::os::unix::fs::PermissionsExt;
         let curr_tab = &mut context.tabs[context.curr_tab_index];
         if let Some(file) = curr_tab.curr_list.get_curr_mut() {
@@ -85,7 +96,7 @@ impl LllRunnable for SetMode {
             mode_string.remove(0);
 
             self.set_mode(file, mode_string);
-            CursorMoveDown::new(1).execute(context, view)
+            CursorMoveDown::new(1).execute(context, view, None)
         } else {
             Ok(())
         }
@@ -0,0 +1,101 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use crate::commands::{LllCommand, LllRunnable, ReloadDirList};
+use crate::context::LllContext;
+use crate::error::LllError;
+use crate::window::LllView;
+
+#[derive(Clone, Debug)]
+pub struct Shell;
+
+impl Shell {
+    pub fn new() -> Self {
+        Shell
+    }
+    pub const fn command() -> &'static str {
+        "shell"
+    }
+
+    pub fn shell(context: &mut LllContext, view: &LllView) -> std::io::Result<()> {
+        let program = match env::var_os("SHELL") {
+            Some(s) => s,
+            None => "sh".into(),
+        };
+        // lets nested shells tell that they're running inside of us
+        let level = match env::var("LLL_LEVEL") {
+            Ok(s) => s.parse::<usize>().unwrap_or(0) + 1,
+            Err(_) => 1,
+        };
+
+        let curr_tab = &context.tabs[context.curr_tab_index];
+        let selection: Vec<String> = curr_tab
+            .curr_list
+            .get_selected_paths()
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect();
+
+        let mut command = process::Command::new(program);
+        command
+            .current_dir(&curr_tab.curr_path)
+            .env("LLL_SELECTION", selection.join("\n"))
+            .env("LLL_LEVEL", level.to_string());
+
+        if let Some(tty) = Self::captured_stdout_tty() {
+            command.stdout(tty);
+        }
+
+        ncurses::savetty();
+        ncurses::endwin();
+        let status = command.status();
+        ncurses::resetty();
+        ncurses::refresh();
+        status?;
+
+        Self::reload_tabs(context, view)
+    }
+
+    // with stdout captured (--choosefiles -), programs we hand the terminal
+    // to write to it directly so their output isn't picked along with files
+    pub fn captured_stdout_tty() -> Option<fs::File> {
+        if unsafe { libc::isatty(libc::STDOUT_FILENO) } != 0 {
+            return None;
+        }
+        fs::OpenOptions::new().write(true).open("/dev/tty").ok()
+    }
+
+    // anything could have changed while we were away
+    pub fn reload_tabs(context: &mut LllContext, view: &LllView) -> std::io::Result<()> {
+        for i in 0..context.tabs.len() {
+            ReloadDirList::reload(i, context)?;
+        }
+        let curr_tab = &mut context.tabs[context.curr_tab_index];
+        curr_tab.refresh(view, &context.config_t, &context.usercache);
+        ncurses::doupdate();
+        Ok(())
+    }
+}
+
+impl LllCommand for Shell {}
+
+impl std::fmt::Display for Shell {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl LllRunnable for Shell {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
+        match Self::shell(context, view) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(LllError::IO(e)),
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use crate::commands::{LllCommand, LllRunnable, Shell};
+use crate::context::LllContext;
+use crate::error::LllError;
+use crate::window::LllView;
+
+pub struct ShellJob {
+    pub command: String,
+    pub child: process::Child,
+}
+
+#[derive(Clone, Debug)]
+pub struct ShellExec {
+    command: String,
+    wait: bool,
+    background: bool,
+}
+
+impl ShellExec {
+    pub fn new(command: String, wait: bool, background: bool) -> Self {
+        ShellExec {
+            command,
+            wait,
+            background,
+        }
+    }
+    pub const fn command() -> &'static str {
+        "shell_exec"
+    }
+
+    // a leading -w waits for enter once the command exits,
+    // a trailing & runs it in the background instead
+    pub fn parse(line: &str) -> Self {
+        let mut line = line.trim();
+        let mut wait = false;
+        let mut background = false;
+
+        if line.starts_with("-w ") {
+            wait = true;
+            line = line[2..].trim_start();
+        }
+        // a command ending in && is left alone, it isn't finished yet
+        if line.ends_with('&') && !line.ends_with("&&") {
+            background = true;
+            line = line[..line.len() - 1].trim_end();
+        }
+        Self::new(String::from(line), wait, background)
+    }
+
+    // arguments go in as quoted words, before any & that backgrounds the command
+    pub fn push_args(&mut self, args: &[String]) {
+        for arg in args.iter() {
+            self.command.push(' ');
+            // a % in an argument is meant literally, not as a placeholder
+            self.command.push_str(&shell_quote(arg).replace('%', "%%"));
+        }
+    }
+
+    pub fn shell_exec(&self, context: &mut LllContext, view: &LllView) -> io::Result<()> {
+        if self.command.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "No command given",
+            ));
+        }
+
+        let curr_tab = &context.tabs[context.curr_tab_index];
+        let curr_list = &curr_tab.curr_list;
+        let command_line = expand_command(
+            &self.command,
+            &curr_list.get_selected_paths(),
+            curr_list
+                .get_curr_ref()
+                .map(|entry| entry.file_path().as_path()),
+            &curr_tab.curr_path,
+        );
+
+        let mut command = process::Command::new("sh");
+        command
+            .arg("-c")
+            .arg(&command_line)
+            .current_dir(&curr_tab.curr_path);
+
+        if self.background {
+            command
+                .stdin(process::Stdio::null())
+                .stdout(process::Stdio::null())
+                .stderr(process::Stdio::null());
+            let child = command.spawn()?;
+            context.jobs.push(ShellJob {
+                command: command_line,
+                child,
+            });
+            return Ok(());
+        }
+
+        // with stdout captured (--choosefiles -), the command and the prompt
+        // write to the terminal instead
+        let captured = unsafe { libc::isatty(libc::STDOUT_FILENO) } == 0;
+        let tty = Shell::captured_stdout_tty();
+        if let Some(tty) = tty.as_ref() {
+            command.stdout(tty.try_clone()?);
+        }
+
+        ncurses::savetty();
+        ncurses::endwin();
+        let status = command.status();
+        if self.wait {
+            let mut prompt: Box<dyn Write> = match tty {
+                Some(s) => Box::new(s),
+                None if captured => Box::new(io::stderr()),
+                None => Box::new(io::stdout()),
+            };
+            write!(prompt, "\nPress ENTER to continue")?;
+            prompt.flush()?;
+            let mut buf = String::new();
+            io::stdin().lock().read_line(&mut buf)?;
+        }
+        ncurses::resetty();
+        ncurses::refresh();
+        status?;
+
+        Shell::reload_tabs(context, view)
+    }
+}
+
+impl LllCommand for ShellExec {
+    fn repeatable(&self) -> bool {
+        true
+    }
+}
+
+impl std::fmt::Display for ShellExec {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", Self::command())?;
+        if self.wait {
+            write!(f, " -w")?;
+        }
+        write!(f, " {}", self.command)?;
+        if self.background {
+            write!(f, " &")?;
+        }
+        Ok(())
+    }
+}
+
+impl LllRunnable for ShellExec {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
+        match self.shell_exec(context, view) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(LllError::IO(e)),
+        }
+    }
+}
+
+// wraps a path in single quotes so sh takes it literally
+pub fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+// %s: selected paths, %f: file under the cursor, %d: current directory,
+// %%: a literal %
+pub fn expand_command(
+    command: &str,
+    selected: &[&PathBuf],
+    curr_file: Option<&Path>,
+    curr_dir: &Path,
+) -> String {
+    let mut expanded = String::with_capacity(command.len());
+    let mut chars = command.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '%' {
+            expanded.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => {
+                let paths: Vec<String> = selected
+                    .iter()
+                    .map(|path| shell_quote(&path.to_string_lossy()))
+                    .collect();
+                expanded.push_str(&paths.join(" "));
+            }
+            Some('f') => {
+                if let Some(path) = curr_file {
+                    expanded.push_str(&shell_quote(&path.to_string_lossy()));
+                }
+            }
+            Some('d') => expanded.push_str(&shell_quote(&curr_dir.to_string_lossy())),
+            Some('%') => expanded.push('%'),
+            Some(c) => {
+                expanded.push('%');
+                expanded.push(c);
+            }
+            None => expanded.push('%'),
+        }
+    }
+    expanded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quote_paths() {
+        assert_eq!(shell_quote("a b"), "'a b'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
+
+    #[test]
+    fn expand_placeholders() {
+        let a = PathBuf::from("/tmp/a b");
+        let b = PathBuf::from("/tmp/c");
+        let expanded = expand_command(
+            "cp %s %d; echo %f 100%% %x",
+            &[&a, &b],
+            Some(Path::new("/tmp/c")),
+            Path::new("/tmp"),
+        );
+        assert_eq!(
+            expanded,
+            "cp '/tmp/a b' '/tmp/c' '/tmp'; echo '/tmp/c' 100% %x"
+        );
+    }
+
+    #[test]
+    fn parse_variants() {
+        let exec = ShellExec::parse("-w du -sh %s");
+        assert!(exec.wait && !exec.background);
+        assert_eq!(exec.command, "du -sh %s");
+
+        let exec = ShellExec::parse("make &");
+        assert!(!exec.wait && exec.background);
+        assert_eq!(exec.command, "make");
+
+        let exec = ShellExec::parse("make &&");
+        assert!(!exec.background);
+        assert_eq!(exec.command, "make &&");
+    }
+
+    #[test]
+    fn push_args_before_background() {
+        let mut exec = ShellExec::parse("mpv &");
+        exec.push_args(&[String::from("a b"), String::from("50%")]);
+        assert!(exec.background);
+        assert_eq!(exec.command, "mpv 'a b' '50%%'");
+    }
+}
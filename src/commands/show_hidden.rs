@@ -34,8 +34,13 @@ impl std::fmt::Display for ToggleHiddenFiles {
 }
 
 impl LllRunnable for ToggleHiddenFiles {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
         Self::toggle_hidden(context);
-        ReloadDirList::new().execute(context, view)
+        ReloadDirList::new().execute(context, view, None)
     }
 }
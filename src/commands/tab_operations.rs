@@ -0,0 +1,158 @@
+use std::path::PathBuf;
+
+use crate::commands::{self, LllCommand, LllRunnable, Quit, TabSwitch};
+use crate::context::LllContext;
+use crate::error::LllError;
+use crate::tab::LllTab;
+use crate::window::LllView;
+
+use crate::HOME_DIR;
+
+fn insert_tab(context: &mut LllContext, view: &LllView, tab: LllTab) -> std::io::Result<()> {
+    let new_index = context.curr_tab_index + 1;
+    context.tabs.insert(new_index, tab);
+    commands::tab_inserted(context, new_index);
+    TabSwitch::tab_switch(new_index, context, view)
+}
+
+#[derive(Clone, Debug)]
+pub struct NewTab {
+    path: Option<PathBuf>,
+}
+
+impl NewTab {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        NewTab { path }
+    }
+    pub const fn command() -> &'static str {
+        "tab_new"
+    }
+
+    pub fn new_tab(
+        path: Option<&PathBuf>,
+        context: &mut LllContext,
+        view: &LllView,
+    ) -> std::io::Result<()> {
+        let curr_path = &context.curr_tab_ref().curr_path;
+        let path = match path {
+            Some(s) => curr_path.join(s).canonicalize()?,
+            None => match HOME_DIR.as_ref() {
+                Some(s) => s.clone(),
+                None => curr_path.clone(),
+            },
+        };
+        let tab = LllTab::new(path, &context.config_t.sort_option)?;
+        insert_tab(context, view, tab)
+    }
+}
+
+impl LllCommand for NewTab {}
+
+impl std::fmt::Display for NewTab {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.path.as_ref() {
+            Some(s) => write!(f, "{} {}", Self::command(), s.to_string_lossy()),
+            None => f.write_str(Self::command()),
+        }
+    }
+}
+
+impl LllRunnable for NewTab {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
+        match Self::new_tab(self.path.as_ref(), context, view) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(LllError::IO(e)),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CloseTab;
+
+impl CloseTab {
+    pub fn new() -> Self {
+        CloseTab
+    }
+    pub const fn command() -> &'static str {
+        "tab_close"
+    }
+
+    pub fn close_tab(context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
+        if context.tabs.len() <= 1 {
+            return Quit::quit(context);
+        }
+
+        let index = context.curr_tab_index;
+        context.tabs.remove(index);
+        commands::tab_removed(context, index);
+
+        let new_index = if index < context.tabs.len() {
+            index
+        } else {
+            context.tabs.len() - 1
+        };
+        match TabSwitch::tab_switch(new_index, context, view) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(LllError::IO(e)),
+        }
+    }
+}
+
+impl LllCommand for CloseTab {}
+
+impl std::fmt::Display for CloseTab {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl LllRunnable for CloseTab {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
+        Self::close_tab(context, view)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DuplicateTab;
+
+impl DuplicateTab {
+    pub fn new() -> Self {
+        DuplicateTab
+    }
+    pub const fn command() -> &'static str {
+        "tab_duplicate"
+    }
+}
+
+impl LllCommand for DuplicateTab {}
+
+impl std::fmt::Display for DuplicateTab {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl LllRunnable for DuplicateTab {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        _: Option<usize>,
+    ) -> Result<(), LllError> {
+        let tab = context.curr_tab_ref().clone();
+        match insert_tab(context, view, tab) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(LllError::IO(e)),
+        }
+    }
+}
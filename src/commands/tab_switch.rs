@@ -34,7 +34,7 @@ impl TabSwitch {
                     .curr_list
                     .update_contents(&context.config_t.sort_option)?;
             }
-            curr_tab.refresh(view, &context.config_t);
+            curr_tab.refresh(view, &context.config_t, &context.usercache);
         }
         ui::redraw_tab_view(&view.tab_win, &context);
         ncurses::doupdate();
@@ -51,16 +51,17 @@ impl std::fmt::Display for TabSwitch {
 }
 
 impl LllRunnable for TabSwitch {
-    fn execute(&self, context: &mut LllContext, view: &LllView) -> Result<(), LllError> {
-        let mut new_index = context.curr_tab_index as i32 + self.movement;
-        let tab_len = context.tabs.len() as i32;
-        while new_index < 0 {
-            new_index += tab_len;
-        }
-        while new_index >= tab_len {
-            new_index -= tab_len;
-        }
-        let new_index = new_index as usize;
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        count: Option<usize>,
+    ) -> Result<(), LllError> {
+        let tab_len = context.tabs.len();
+        // going around every tab ends up where it started
+        let steps = (count.unwrap_or(1) % tab_len) as i64;
+        let new_index = (context.curr_tab_index as i64 + self.movement as i64 * steps)
+            .rem_euclid(tab_len as i64) as usize;
         match Self::tab_switch(new_index, context, view) {
             Ok(_) => Ok(()),
             Err(e) => Err(LllError::IO(e)),
@@ -0,0 +1,101 @@
+use lazy_static::lazy_static;
+use std::sync::atomic;
+
+use crate::commands::{self, LllCommand, LllRunnable, ShellExec};
+use crate::config::LllUserCommand;
+use crate::context::LllContext;
+use crate::error::{KeymapError, LllError};
+use crate::window::LllView;
+
+// stops user commands that end up calling themselves
+const MAX_DEPTH: usize = 16;
+
+lazy_static! {
+    static ref DEPTH: atomic::AtomicUsize = atomic::AtomicUsize::new(0);
+}
+
+#[derive(Clone, Debug)]
+pub struct UserCommand {
+    command: LllUserCommand,
+    args: Vec<String>,
+}
+
+impl UserCommand {
+    pub fn new(command: LllUserCommand, args: Vec<String>) -> Self {
+        UserCommand { command, args }
+    }
+
+    fn run(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        count: Option<usize>,
+    ) -> Result<(), LllError> {
+        if let Some(template) = self.command.shell.as_ref() {
+            let mut exec = ShellExec::parse(template);
+            exec.push_args(&self.args);
+            return exec.execute(context, view, None);
+        }
+
+        for line in self.command.commands.iter() {
+            match commands::parse_command(line) {
+                Ok(s) => s.execute(context, view, count)?,
+                Err(e) => return Err(LllError::Keymap(e)),
+            }
+            if context.exit {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl LllCommand for UserCommand {
+    // repeatable if anything it runs is, same as a chain
+    fn repeatable(&self) -> bool {
+        if self.command.shell.is_some() {
+            return true;
+        }
+        if DEPTH.fetch_add(1, atomic::Ordering::SeqCst) >= MAX_DEPTH {
+            DEPTH.fetch_sub(1, atomic::Ordering::SeqCst);
+            return false;
+        }
+        let repeatable = self.command.commands.iter().any(|line| {
+            commands::parse_command(line)
+                .map(|command| command.repeatable())
+                .unwrap_or(false)
+        });
+        DEPTH.fetch_sub(1, atomic::Ordering::SeqCst);
+        repeatable
+    }
+}
+
+impl std::fmt::Display for UserCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.command.name)?;
+        for arg in self.args.iter() {
+            write!(f, " {}", arg)?;
+        }
+        Ok(())
+    }
+}
+
+impl LllRunnable for UserCommand {
+    fn execute(
+        &self,
+        context: &mut LllContext,
+        view: &LllView,
+        count: Option<usize>,
+    ) -> Result<(), LllError> {
+        if DEPTH.fetch_add(1, atomic::Ordering::SeqCst) >= MAX_DEPTH {
+            DEPTH.fetch_sub(1, atomic::Ordering::SeqCst);
+            return Err(LllError::Keymap(KeymapError::new(
+                None,
+                format!("{}: commands nested too deeply", self.command.name),
+            )));
+        }
+        let res = self.run(context, view, count);
+        DEPTH.fetch_sub(1, atomic::Ordering::SeqCst);
+        res
+    }
+}
@@ -0,0 +1,138 @@
+use rustyline::completion::{Completer, FilenameCompleter, Pair};
+use rustyline::line_buffer::LineBuffer;
+
+use crate::commands::{self, CommandChain};
+use crate::prompt_history::PromptKind;
+
+use crate::USERCOMMANDS_T;
+
+// flags each command takes
+const COMMAND_FLAGS: &[(&str, &[&str])] = &[
+    ("paste_files", &["--overwrite", "--skip_exist"]),
+    ("select_files", &["--toggle", "--all"]),
+];
+
+// commands whose arguments are paths, user commands are taken to be too
+const PATH_COMMANDS: &[&str] = &["cd", "mkdir", "rename", "shell_exec", "tab_new"];
+
+// the console completes command names and flags, everything else and the
+// arguments of commands that take paths complete as file names
+pub struct LllCompleter {
+    kind: PromptKind,
+    filename: FilenameCompleter,
+}
+
+impl LllCompleter {
+    pub fn new(kind: PromptKind) -> Self {
+        LllCompleter {
+            kind,
+            filename: FilenameCompleter::new(),
+        }
+    }
+
+    pub fn complete(&self, line: &str, pos: usize) -> rustyline::Result<(usize, Vec<Pair>)> {
+        if self.kind == PromptKind::Console {
+            let user_commands: Vec<&str> = USERCOMMANDS_T.keys().map(String::as_str).collect();
+            if let Some((start, names)) = complete_command(&line[..pos], &user_commands) {
+                let candidates = names
+                    .into_iter()
+                    .map(|name| Pair {
+                        display: name.clone(),
+                        replacement: name,
+                    })
+                    .collect();
+                return Ok((start, candidates));
+            }
+        }
+        self.filename.complete_path(line, pos)
+    }
+
+    pub fn update(&self, line_buffer: &mut LineBuffer, start: usize, elected: &str) {
+        self.filename.update(line_buffer, start, elected)
+    }
+}
+
+// completes the last command of a chain as typed up to the cursor, giving
+// where the completed word starts and its candidates, or None for a path
+fn complete_command(text: &str, user_commands: &[&str]) -> Option<(usize, Vec<String>)> {
+    // a chain ending in ; has an empty command after it
+    let segment = match CommandChain::split(text).last() {
+        Some(s) if text.ends_with(s) => s.trim_start(),
+        _ => "",
+    };
+    let offset = text.len() - segment.len();
+    if segment.starts_with('!') {
+        return None;
+    }
+
+    let word_start = match segment.rfind(char::is_whitespace) {
+        Some(i) => i + 1,
+        None => {
+            let mut names: Vec<String> = commands::COMMANDS
+                .iter()
+                .map(|(name, _)| name)
+                .chain(user_commands.iter())
+                .filter(|name| name.starts_with(segment))
+                .map(|name| String::from(*name))
+                .collect();
+            names.sort();
+            names.dedup();
+            return Some((offset, names));
+        }
+    };
+
+    let command = segment.split_whitespace().next().unwrap_or("");
+    let word = &segment[word_start..];
+    if word.starts_with('-') {
+        if let Some((_, flags)) = COMMAND_FLAGS.iter().find(|(name, _)| *name == command) {
+            let flags = flags
+                .iter()
+                .filter(|flag| flag.starts_with(word))
+                .map(|flag| String::from(*flag))
+                .collect();
+            return Some((offset + word_start, flags));
+        }
+    }
+
+    if PATH_COMMANDS.contains(&command) || user_commands.contains(&command) {
+        None
+    } else {
+        Some((text.len(), Vec::new()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn complete_names() {
+        assert_eq!(
+            complete_command("tab_sw", &[]),
+            Some((0, vec![String::from("tab_switch")]))
+        );
+        assert_eq!(
+            complete_command("select_files --all; tr", &["trash"]),
+            Some((20, vec![String::from("trash")]))
+        );
+    }
+
+    #[test]
+    fn complete_flags() {
+        assert_eq!(
+            complete_command("paste_files --o", &[]),
+            Some((12, vec![String::from("--overwrite")]))
+        );
+        assert_eq!(
+            complete_command("tab_switch 1", &[]),
+            Some((12, Vec::new()))
+        );
+    }
+
+    #[test]
+    fn complete_paths() {
+        assert_eq!(complete_command("cd /tm", &[]), None);
+        assert_eq!(complete_command("trash a", &["trash"]), None);
+        assert_eq!(complete_command("cd /; !ls /tm", &[]), None);
+    }
+}
@@ -1,7 +1,10 @@
 use serde_derive::Deserialize;
+use std::time;
 
 use super::{parse_to_config_file, ConfigStructure, Flattenable};
 use crate::sort;
+use crate::status::LllStatusFormat;
+use crate::ui::DetailColumn;
 
 use crate::CONFIG_FILE;
 
@@ -14,6 +17,15 @@ const fn default_scroll_offset() -> usize {
 const fn default_max_preview_size() -> u64 {
     2 * 1024 * 1024 // 2 MB
 }
+const fn default_preview_timeout() -> u64 {
+    2000 // milliseconds
+}
+const fn default_preview_cache_size() -> usize {
+    8 * 1024 * 1024 // 8 MB
+}
+fn default_detail_columns() -> Vec<DetailColumn> {
+    vec![DetailColumn::Size]
+}
 const fn default_column_ratio() -> (usize, usize, usize) {
     (1, 3, 4)
 }
@@ -57,9 +69,21 @@ impl std::default::Default for SortRawOption {
 pub struct LllRawConfig {
     #[serde(default = "default_scroll_offset")]
     scroll_offset: usize,
+    #[serde(default = "default_true")]
+    show_preview: bool,
+    #[serde(default)]
+    save_session: bool,
+    #[serde(default)]
+    save_macros: bool,
     #[serde(default = "default_max_preview_size")]
     max_preview_size: u64,
+    #[serde(default = "default_preview_timeout")]
+    preview_timeout: u64,
+    #[serde(default = "default_preview_cache_size")]
+    preview_cache_size: usize,
     column_ratio: Option<[usize; 3]>,
+    detail_columns: Option<Vec<String>>,
+    status_format: Option<String>,
     sort_method: Option<String>,
     #[serde(default)]
     sort_option: SortRawOption,
@@ -72,6 +96,31 @@ impl Flattenable<LllConfig> for LllRawConfig {
             _ => default_column_ratio(),
         };
 
+        let detail_columns = match self.detail_columns {
+            Some(s) => s
+                .iter()
+                .filter_map(|column| {
+                    let parsed = DetailColumn::parse(column.as_str());
+                    if parsed.is_none() {
+                        eprintln!("Unknown detail column: {}", column);
+                    }
+                    parsed
+                })
+                .collect(),
+            None => default_detail_columns(),
+        };
+
+        let status_format = match self.status_format {
+            Some(s) => match LllStatusFormat::parse(s.as_str()) {
+                Ok(s) => s,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    LllStatusFormat::default()
+                }
+            },
+            None => LllStatusFormat::default(),
+        };
+
         let sort_method = match self.sort_method {
             Some(s) => match sort::SortType::parse(s.as_str()) {
                 Some(s) => s,
@@ -83,8 +132,15 @@ impl Flattenable<LllConfig> for LllRawConfig {
 
         LllConfig {
             scroll_offset: self.scroll_offset,
+            show_preview: self.show_preview,
+            save_session: self.save_session,
+            save_macros: self.save_macros,
             max_preview_size: self.max_preview_size,
+            preview_timeout: time::Duration::from_millis(self.preview_timeout),
+            preview_cache_size: self.preview_cache_size,
             column_ratio,
+            detail_columns,
+            status_format,
             sort_option,
         }
     }
@@ -93,9 +149,16 @@ impl Flattenable<LllConfig> for LllRawConfig {
 #[derive(Debug, Clone)]
 pub struct LllConfig {
     pub scroll_offset: usize,
+    pub show_preview: bool,
+    pub save_session: bool,
+    pub save_macros: bool,
     pub max_preview_size: u64,
+    pub preview_timeout: time::Duration,
+    pub preview_cache_size: usize,
     pub sort_option: sort::SortOption,
     pub column_ratio: (usize, usize, usize),
+    pub detail_columns: Vec<DetailColumn>,
+    pub status_format: LllStatusFormat,
 }
 
 impl ConfigStructure for LllConfig {
@@ -111,9 +174,16 @@ impl std::default::Default for LllConfig {
 
         LllConfig {
             scroll_offset: default_scroll_offset(),
+            show_preview: default_true(),
+            save_session: false,
+            save_macros: false,
             max_preview_size: default_max_preview_size(),
+            preview_timeout: time::Duration::from_millis(default_preview_timeout()),
+            preview_cache_size: default_preview_cache_size(),
             sort_option,
             column_ratio: default_column_ratio(),
+            detail_columns: default_detail_columns(),
+            status_format: LllStatusFormat::default(),
         }
     }
 }
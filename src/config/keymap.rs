@@ -52,6 +52,42 @@ const fn default_tab() -> i32 {
     '\t' as i32
 }
 
+const fn ctrl(ch: char) -> i32 {
+    ch as i32 & 0x1F
+}
+
+const fn default_line_start() -> i32 {
+    ctrl('a')
+}
+
+const fn default_line_end() -> i32 {
+    ctrl('e')
+}
+
+const fn default_kill_word() -> i32 {
+    ctrl('w')
+}
+
+const fn default_kill_line_start() -> i32 {
+    ctrl('u')
+}
+
+const fn default_kill_line_end() -> i32 {
+    ctrl('k')
+}
+
+const fn default_yank() -> i32 {
+    ctrl('y')
+}
+
+const fn default_word_backward() -> i32 {
+    'b' as i32
+}
+
+const fn default_word_forward() -> i32 {
+    'f' as i32
+}
+
 #[derive(Debug, Deserialize)]
 struct LllRawKeymapping {
     #[serde(default)]
@@ -84,6 +120,23 @@ pub struct LllKeyMapping {
     pub escape: i32,
     #[serde(default = "default_tab")]
     pub tab: i32,
+    #[serde(default = "default_line_start")]
+    pub line_start: i32,
+    #[serde(default = "default_line_end")]
+    pub line_end: i32,
+    #[serde(default = "default_kill_word")]
+    pub kill_word: i32,
+    #[serde(default = "default_kill_line_start")]
+    pub kill_line_start: i32,
+    #[serde(default = "default_kill_line_end")]
+    pub kill_line_end: i32,
+    #[serde(default = "default_yank")]
+    pub yank: i32,
+    // pressed together with alt
+    #[serde(default = "default_word_backward")]
+    pub word_backward: i32,
+    #[serde(default = "default_word_forward")]
+    pub word_forward: i32,
 }
 
 impl std::default::Default for LllKeyMapping {
@@ -100,6 +153,14 @@ impl std::default::Default for LllKeyMapping {
             enter: default_enter(),
             escape: default_escape(),
             tab: default_tab(),
+            line_start: default_line_start(),
+            line_end: default_line_end(),
+            kill_word: default_kill_word(),
+            kill_line_start: default_kill_line_start(),
+            kill_line_end: default_kill_line_end(),
+            yank: default_yank(),
+            word_backward: default_word_backward(),
+            word_forward: default_word_forward(),
         }
     }
 }
@@ -137,8 +198,14 @@ impl Flattenable<LllCommandMapping> for LllRawCommandMapping {
     fn flatten(self) -> LllCommandMapping {
         let mut keymaps = LllCommandMapping::new();
         self.mapcommand.iter().for_each(|m| {
-            let args: Vec<&str> = m.args.iter().map(String::as_str).collect();
-            match commands::from_args(m.command.as_str(), &args) {
+            // without args, command is a console line and may chain commands
+            let command = if m.args.is_empty() {
+                commands::parse_command(m.command.as_str())
+            } else {
+                let args: Vec<&str> = m.args.iter().map(String::as_str).collect();
+                commands::from_args(m.command.as_str(), &args)
+            };
+            match command {
                 Ok(command) => insert_keycommand(&mut keymaps, command, &m.keys[..]),
                 Err(e) => eprintln!("{}", e),
             }
@@ -149,6 +216,51 @@ impl Flattenable<LllCommandMapping> for LllRawCommandMapping {
 
 pub type LllCommandMapping = HashMap<i32, CommandKeybind>;
 
+// a named command made of either built-in commands or a shell template
+#[derive(Clone, Debug, Deserialize)]
+pub struct LllUserCommand {
+    pub name: String,
+    pub shell: Option<String>,
+    #[serde(default)]
+    pub commands: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LllRawUserCommands {
+    #[serde(default)]
+    command: Vec<LllUserCommand>,
+}
+
+impl Flattenable<LllUserCommands> for LllRawUserCommands {
+    fn flatten(self) -> LllUserCommands {
+        let mut usercommands = LllUserCommands::new();
+        for command in self.command {
+            if command.shell.is_some() != command.commands.is_empty() {
+                eprintln!(
+                    "Error: command {} needs exactly one of shell or commands",
+                    command.name
+                );
+                continue;
+            }
+            if usercommands.contains_key(&command.name) {
+                eprintln!("Error: command {} defined more than once", command.name);
+                continue;
+            }
+            usercommands.insert(command.name.clone(), command);
+        }
+        usercommands
+    }
+}
+
+pub type LllUserCommands = HashMap<String, LllUserCommand>;
+
+impl ConfigStructure for LllUserCommands {
+    fn get_config() -> Self {
+        parse_to_config_file::<LllRawUserCommands, LllUserCommands>(KEYMAP_FILE)
+            .unwrap_or_else(LllUserCommands::default)
+    }
+}
+
 impl ConfigStructure for LllCommandMapping {
     fn get_config() -> Self {
         parse_to_config_file::<LllRawCommandMapping, LllCommandMapping>(KEYMAP_FILE)
@@ -5,7 +5,7 @@ pub mod preview;
 pub mod theme;
 
 pub use self::config::LllConfig;
-pub use self::keymap::{LllCommandMapping, LllKeyMapping};
+pub use self::keymap::{LllCommandMapping, LllKeyMapping, LllUserCommand, LllUserCommands};
 pub use self::mimetype::LllMimetype;
 pub use self::preview::{LllPreview, LllPreviewEntry};
 pub use self::theme::{LllColorTheme, LllTheme};
@@ -1,13 +1,29 @@
-use crate::commands::FileOperationThread;
+use std::path::PathBuf;
+
+use users::UsersCache;
+
+use crate::commands::{FileOperationThread, LllCommand, ShellJob};
 use crate::config;
+use crate::preview::{PreviewCache, PreviewWorker};
 use crate::tab::LllTab;
 
 pub struct LllContext {
     pub threads: Vec<FileOperationThread<u64, fs_extra::TransitProcess>>,
+    pub jobs: Vec<ShellJob>,
     pub curr_tab_index: usize,
     pub tabs: Vec<LllTab>,
+    pub preview: PreviewWorker,
+    pub preview_cache: PreviewCache,
     pub exit: bool,
+    // picked with the choose command, written out once we've exited
+    pub chosen_files: Option<Vec<PathBuf>>,
+    // the last command that changed something, with its count, for repeat_last
+    pub last_command: Option<(Box<dyn LllCommand>, Option<usize>)>,
+    // every key sequence and the command it runs, for help
+    pub keybindings: Vec<(String, String)>,
 
+    // user and group names, looked up once and kept for the whole session
+    pub usercache: UsersCache,
     pub config_t: config::LllConfig,
 }
 
@@ -15,9 +31,16 @@ impl LllContext {
     pub fn new(config_t: config::LllConfig) -> Self {
         LllContext {
             threads: Vec::new(),
+            jobs: Vec::new(),
             curr_tab_index: 0,
             tabs: Vec::new(),
+            preview: PreviewWorker::new(),
+            preview_cache: PreviewCache::new(config_t.preview_cache_size),
             exit: false,
+            chosen_files: None,
+            last_command: None,
+            keybindings: Vec::new(),
+            usercache: UsersCache::new(),
             config_t,
         }
     }
@@ -4,7 +4,7 @@ use crate::fs::{LllDirEntry, LllMetadata};
 use crate::sort;
 use crate::window::LllPageState;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct LllDirList {
     pub index: Option<usize>,
     path: path::PathBuf,
@@ -22,7 +22,7 @@ impl LllDirList {
         let index = if contents.is_empty() { None } else { Some(0) };
 
         let metadata = LllMetadata::from(&path)?;
-        let pagestate = LllPageState::default();
+        let pagestate = LllPageState::new();
 
         Ok(LllDirList {
             index,
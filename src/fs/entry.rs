@@ -1,12 +1,14 @@
 use std::{fs, path};
 
 use crate::fs::LllMetadata;
+use crate::sort;
 
 #[derive(Clone)]
 pub struct LllDirEntry {
     name: String,
     path: path::PathBuf,
     pub metadata: LllMetadata,
+    children: Option<usize>,
     selected: bool,
     marked: bool,
 }
@@ -30,6 +32,7 @@ impl LllDirEntry {
             name,
             path,
             metadata,
+            children: None,
             selected: false,
             marked: false,
         };
@@ -44,6 +47,17 @@ impl LllDirEntry {
         &self.path
     }
 
+    // number of entries in a directory, only counted once it's needed
+    pub fn children(&mut self, sort_option: &sort::SortOption) -> Option<usize> {
+        if self.children.is_none() {
+            let filter_func = sort_option.filter_func();
+            self.children = fs::read_dir(&self.path)
+                .ok()
+                .map(|entries| entries.filter(filter_func).count());
+        }
+        self.children
+    }
+
     pub fn is_selected(&self) -> bool {
         self.selected
     }
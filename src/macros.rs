@@ -0,0 +1,202 @@
+use lazy_static::lazy_static;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Mutex;
+
+use crate::session;
+
+const MACROS_FILE: &str = "macros.toml";
+// stops macros that end up playing themselves
+const MAX_PLAYS: usize = 1000;
+
+lazy_static! {
+    static ref MACROS: Mutex<LllMacros> = Mutex::new(LllMacros::default());
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct LllMacroRegisters {
+    registers: HashMap<String, Vec<i32>>,
+}
+
+// a register being played, repeats are replayed from the same keys rather
+// than queued up front, so large counts cost nothing
+#[derive(Debug)]
+struct LllPlayback {
+    keys: Vec<i32>,
+    pos: usize,
+    remaining: usize,
+}
+
+#[derive(Debug, Default)]
+struct LllMacros {
+    registers: HashMap<String, Vec<i32>>,
+    // the register being recorded and the keys typed so far
+    recording: Option<(String, Vec<i32>)>,
+    // where the keys of the command being read start in the recording
+    command_start: usize,
+    // the innermost playback is last
+    playback: Vec<LllPlayback>,
+    plays: usize,
+    // whether the last key read came from a macro rather than the keyboard
+    replayed: bool,
+}
+
+impl LllMacros {
+    fn next_key(&mut self) -> Option<i32> {
+        while let Some(playback) = self.playback.last_mut() {
+            if let Some(ch) = playback.keys.get(playback.pos) {
+                playback.pos += 1;
+                return Some(*ch);
+            }
+            playback.remaining -= 1;
+            if playback.remaining > 0 {
+                playback.pos = 0;
+            } else {
+                self.playback.pop();
+            }
+        }
+        None
+    }
+
+    fn push(&mut self, keys: Vec<i32>, count: usize) {
+        if count > 0 {
+            self.playback.push(LllPlayback {
+                keys,
+                pos: 0,
+                remaining: count,
+            });
+        }
+    }
+}
+
+// every key lll reads goes through here, so macros are played back through
+// composite keybinds, the console and prompts just like typed keys
+pub fn wget_wch(win: ncurses::WINDOW) -> Option<i32> {
+    {
+        let mut macros = MACROS.lock().unwrap();
+        if let Some(ch) = macros.next_key() {
+            macros.replayed = true;
+            return Some(ch);
+        }
+        macros.plays = 0;
+        macros.replayed = false;
+    }
+
+    let ch = match ncurses::wget_wch(win)? {
+        ncurses::WchResult::Char(s) => s as i32,
+        ncurses::WchResult::KeyCode(s) => s,
+    };
+    if ch != ncurses::KEY_RESIZE {
+        if let Some((_, keys)) = MACROS.lock().unwrap().recording.as_mut() {
+            keys.push(ch);
+        }
+    }
+    Some(ch)
+}
+
+// puts a key back to be read again, it was already recorded the first time
+pub fn unget(ch: i32) {
+    MACROS.lock().unwrap().push(vec![ch], 1);
+}
+
+pub fn replayed() -> bool {
+    MACROS.lock().unwrap().replayed
+}
+
+pub fn get_wch() -> Option<i32> {
+    wget_wch(ncurses::stdscr())
+}
+
+pub fn getch() -> i32 {
+    get_wch().unwrap_or(ncurses::ERR)
+}
+
+// called by the main loop before it reads the first key of a command
+pub fn start_command() {
+    let mut macros = MACROS.lock().unwrap();
+    macros.command_start = match macros.recording.as_ref() {
+        Some((_, keys)) => keys.len(),
+        None => 0,
+    };
+}
+
+pub fn start_recording(register: String) {
+    MACROS.lock().unwrap().recording = Some((register, Vec::new()));
+}
+
+// the keys that stopped the recording are left out of it
+pub fn stop_recording() -> Option<String> {
+    let mut macros = MACROS.lock().unwrap();
+    let command_start = macros.command_start;
+    let (register, mut keys) = macros.recording.take()?;
+    keys.truncate(command_start);
+    macros.registers.insert(register.clone(), keys);
+    Some(register)
+}
+
+// queues a register to be played ahead of any keys still waiting
+pub fn play(register: &str, count: usize) -> Result<(), String> {
+    let mut macros = MACROS.lock().unwrap();
+    let keys = match macros.registers.get(register) {
+        Some(s) if !s.is_empty() => s.clone(),
+        _ => return Err(format!("Register {} is empty", register)),
+    };
+    macros.plays += 1;
+    if macros.plays > MAX_PLAYS {
+        macros.playback.clear();
+        return Err(format!("{}: macros nested too deeply", register));
+    }
+    macros.push(keys, count);
+    Ok(())
+}
+
+pub fn load() -> std::io::Result<()> {
+    let contents = match fs::read_to_string(session::state_file(MACROS_FILE)?) {
+        Ok(s) => s,
+        Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    let saved: LllMacroRegisters = match toml::from_str(&contents) {
+        Ok(s) => s,
+        Err(e) => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Error parsing {}: {}", MACROS_FILE, e),
+            ));
+        }
+    };
+    MACROS.lock().unwrap().registers.extend(saved.registers);
+    Ok(())
+}
+
+pub fn save() -> std::io::Result<()> {
+    let saved = LllMacroRegisters {
+        registers: MACROS.lock().unwrap().registers.clone(),
+    };
+    let contents = match toml::to_string(&saved) {
+        Ok(s) => s,
+        Err(e) => return Err(std::io::Error::new(std::io::ErrorKind::Other, e)),
+    };
+
+    let file_path = session::state_file(MACROS_FILE)?;
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(file_path, contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn playback_repeats_and_nests() {
+        let mut macros = LllMacros::default();
+        macros.push(vec![1, 2], 2);
+        assert_eq!(macros.next_key(), Some(1));
+        macros.push(vec![3], 1);
+        let keys: Vec<i32> = std::iter::from_fn(|| macros.next_key()).collect();
+        assert_eq!(keys, vec![3, 2, 1, 2]);
+    }
+}
@@ -1,11 +1,17 @@
 mod commands;
+mod completion;
 mod config;
 mod context;
 mod error;
 mod fs;
 mod history;
+mod macros;
+mod preview;
+mod prompt_history;
 mod run;
+mod session;
 mod sort;
+mod status;
 mod tab;
 mod textfield;
 mod ui;
@@ -17,7 +23,8 @@ use std::path::PathBuf;
 use structopt::StructOpt;
 
 use config::{
-    ConfigStructure, LllCommandMapping, LllConfig, LllKeyMapping, LllMimetype, LllPreview, LllTheme,
+    ConfigStructure, LllCommandMapping, LllConfig, LllKeyMapping, LllMimetype, LllPreview,
+    LllTheme, LllUserCommands,
 };
 use run::run;
 
@@ -29,9 +36,16 @@ const THEME_FILE: &str = "theme.toml";
 const PREVIEW_FILE: &str = "preview.toml";
 
 lazy_static! {
+    static ref ARGS: Args = Args::from_args();
+
     // dynamically builds the config hierarchy
     static ref CONFIG_HIERARCHY: Vec<PathBuf> = {
         let mut temp = vec![];
+        // a config directory given on the command line replaces the hierarchy
+        if let Some(config_dir) = ARGS.config_dir.as_ref() {
+            temp.push(config_dir.clone());
+            return temp;
+        }
         // TODO: get rid of this, zero config files are the goal
         match xdg::BaseDirectories::with_prefix(PROGRAM_NAME) {
             Ok(dirs) => temp.push(dirs.get_config_home()),
@@ -47,6 +61,7 @@ lazy_static! {
     static ref MIMETYPE_T: LllMimetype = LllMimetype::get_config();
     static ref PREVIEW_T: LllPreview = LllPreview::get_config();
     static ref KEYMAP_T: LllKeyMapping = LllKeyMapping::get_config();
+    static ref USERCOMMANDS_T: LllUserCommands = LllUserCommands::get_config();
 
     static ref HOME_DIR: Option<PathBuf> = dirs::home_dir();
 }
@@ -55,10 +70,22 @@ lazy_static! {
 pub struct Args {
     #[structopt(short = "d", long = "debug")]
     debug: bool,
+    #[structopt(long = "restore-session")]
+    restore_session: bool,
+    #[structopt(long = "config-dir", parse(from_os_str))]
+    config_dir: Option<PathBuf>,
+    #[structopt(long = "choosedir", parse(from_os_str))]
+    choosedir: Option<PathBuf>,
+    #[structopt(long = "choosefiles", parse(from_os_str))]
+    choosefiles: Option<PathBuf>,
+    #[structopt(short = "0", long = "null")]
+    null: bool,
+    #[structopt(name = "PATH", parse(from_os_str))]
+    paths: Vec<PathBuf>,
 }
 
 fn main() {
-    let args = Args::from_args();
+    let args = &*ARGS;
 
     let config = LllConfig::get_config();
     let keymap = LllCommandMapping::get_config();
@@ -67,7 +94,8 @@ fn main() {
         eprintln!("config: {:#?}", config);
         eprintln!("theme config: {:#?}", *THEME_T);
         eprintln!("mimetype config: {:#?}", *MIMETYPE_T);
+        eprintln!("user commands: {:#?}", *USERCOMMANDS_T);
     }
 
-    run(config, keymap);
+    run(config, keymap, args);
 }
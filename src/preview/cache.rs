@@ -0,0 +1,138 @@
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::time;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PreviewKey {
+    pub path: PathBuf,
+    pub modified: time::SystemTime,
+    pub cols: usize,
+}
+
+// least recently used cache of rendered previews, bounded by
+// the approximate number of bytes held
+#[derive(Debug)]
+pub struct PreviewCache {
+    capacity: usize,
+    size: usize,
+    entries: HashMap<PreviewKey, Vec<String>>,
+    order: VecDeque<PreviewKey>,
+}
+
+impl PreviewCache {
+    pub fn new(capacity: usize) -> Self {
+        PreviewCache {
+            capacity,
+            size: 0,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    pub fn get(&mut self, key: &PreviewKey) -> Option<&Vec<String>> {
+        if self.entries.contains_key(key) {
+            self.touch(key);
+        }
+        self.entries.get(key)
+    }
+
+    pub fn insert(&mut self, key: PreviewKey, lines: Vec<String>) {
+        let entry_size = Self::entry_size(&key, &lines);
+        if entry_size > self.capacity {
+            return;
+        }
+        self.remove(&key);
+        while self.size + entry_size > self.capacity {
+            match self.order.pop_front() {
+                Some(oldest) => {
+                    if let Some(s) = self.entries.remove(&oldest) {
+                        self.size -= Self::entry_size(&oldest, &s);
+                    }
+                }
+                None => break,
+            }
+        }
+        self.size += entry_size;
+        self.order.push_back(key.clone());
+        self.entries.insert(key, lines);
+    }
+
+    fn remove(&mut self, key: &PreviewKey) {
+        if let Some(s) = self.entries.remove(key) {
+            self.size -= Self::entry_size(key, &s);
+            self.order.retain(|k| k != key);
+        }
+    }
+
+    fn touch(&mut self, key: &PreviewKey) {
+        if let Some(i) = self.order.iter().position(|k| k == key) {
+            if let Some(k) = self.order.remove(i) {
+                self.order.push_back(k);
+            }
+        }
+    }
+
+    fn entry_size(key: &PreviewKey, lines: &[String]) -> usize {
+        let path_size = key.path.as_os_str().len();
+        lines.iter().fold(path_size, |acc, line| acc + line.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(path: &str) -> PreviewKey {
+        PreviewKey {
+            path: PathBuf::from(path),
+            modified: time::UNIX_EPOCH,
+            cols: 80,
+        }
+    }
+
+    fn lines(size: usize) -> Vec<String> {
+        vec!["x".repeat(size)]
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = PreviewCache::new(30);
+        cache.insert(key("a"), lines(9));
+        cache.insert(key("b"), lines(9));
+        cache.insert(key("c"), lines(9));
+        assert_eq!(3, cache.entries.len());
+
+        assert!(cache.get(&key("a")).is_some());
+        cache.insert(key("d"), lines(9));
+        assert_eq!(3, cache.entries.len());
+        assert!(cache.get(&key("a")).is_some());
+        assert!(cache.get(&key("b")).is_none());
+    }
+
+    #[test]
+    fn modified_time_is_part_of_key() {
+        let mut cache = PreviewCache::new(100);
+        cache.insert(key("a"), lines(5));
+        let mut newer = key("a");
+        newer.modified = time::UNIX_EPOCH + time::Duration::from_secs(1);
+        assert!(cache.get(&newer).is_none());
+    }
+
+    #[test]
+    fn entries_over_capacity_are_skipped() {
+        let mut cache = PreviewCache::new(10);
+        cache.insert(key("a"), lines(5));
+        cache.insert(key("b"), lines(20));
+        assert_eq!(1, cache.entries.len());
+        assert!(cache.get(&key("a")).is_some());
+    }
+
+    #[test]
+    fn reinserting_replaces_entry() {
+        let mut cache = PreviewCache::new(20);
+        cache.insert(key("a"), lines(5));
+        cache.insert(key("a"), lines(8));
+        assert_eq!(1, cache.entries.len());
+        assert_eq!(Some(&lines(8)), cache.get(&key("a")));
+    }
+}
@@ -0,0 +1,113 @@
+mod cache;
+mod worker;
+
+pub use self::cache::{PreviewCache, PreviewKey};
+pub use self::worker::{PreviewRequest, PreviewWorker};
+
+use std::fs;
+use std::path::Path;
+
+use users::UsersCache;
+
+use crate::config::LllConfig;
+use crate::context::LllContext;
+use crate::history::{DirectoryHistory, LllHistory};
+use crate::ui;
+use crate::window::{LllPanel, LllView};
+
+pub fn preview_curr_entry(context: &mut LllContext, view: &LllView) {
+    let win = &view.right_win;
+    let config_t = &context.config_t;
+    let curr_tab = &mut context.tabs[context.curr_tab_index];
+
+    let key = match curr_tab.curr_list.get_curr_ref() {
+        Some(entry) if config_t.show_preview => PreviewKey {
+            path: entry.file_path().clone(),
+            // the listing's mtime is as old as the listing, the file may
+            // have been changed since
+            modified: fs::metadata(entry.file_path())
+                .and_then(|metadata| metadata.modified())
+                .unwrap_or(entry.metadata.modified),
+            cols: win.cols as usize,
+        },
+        _ => {
+            context.preview.cancel();
+            ncurses::werase(win.win);
+            win.queue_for_refresh();
+            return;
+        }
+    };
+
+    if key.path.is_dir() {
+        context.preview.cancel();
+        preview_directory(
+            &mut curr_tab.history,
+            &key.path,
+            win,
+            config_t,
+            &context.usercache,
+        );
+    } else if !context.preview.is_current(&key) {
+        match context.preview_cache.get(&key) {
+            Some(lines) => {
+                ui::display_text(win, lines);
+                context.preview.set_shown(key);
+            }
+            None => {
+                // clear out the old preview while the new one is being generated
+                ncurses::werase(win.win);
+                win.queue_for_refresh();
+
+                let request = PreviewRequest {
+                    key,
+                    rows: win.rows as usize,
+                    max_preview_size: config_t.max_preview_size,
+                    timeout: config_t.preview_timeout,
+                };
+                context.preview.request(request);
+            }
+        }
+    }
+}
+
+pub fn process_preview(context: &mut LllContext, view: &LllView) {
+    if let Some(result) = context.preview.recv() {
+        let win = &view.right_win;
+        match result.output {
+            Ok(lines) => {
+                ui::display_text(win, &lines);
+                context.preview_cache.insert(result.key, lines);
+            }
+            Err(e) => ui::wprint_err(win, e.to_string().as_str()),
+        }
+        ncurses::doupdate();
+    }
+}
+
+fn preview_directory(
+    history: &mut LllHistory,
+    path: &Path,
+    win: &LllPanel,
+    config_t: &LllConfig,
+    usercache: &UsersCache,
+) {
+    let sort_option = &config_t.sort_option;
+    match history.get_mut_or_create(path, sort_option) {
+        Ok(dirlist) => {
+            if dirlist.need_update() {
+                if let Err(e) = dirlist.update_contents(sort_option) {
+                    ui::wprint_err(win, e.to_string().as_str());
+                    return;
+                }
+            }
+            ui::display_contents(
+                win,
+                dirlist,
+                config_t,
+                usercache,
+                &ui::SECONDARY_DISPLAY_OPTION,
+            );
+        }
+        Err(e) => ui::wprint_err(win, e.to_string().as_str()),
+    }
+}
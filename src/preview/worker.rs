@@ -0,0 +1,279 @@
+use std::fs;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+use std::process;
+use std::sync::mpsc;
+use std::thread;
+use std::time;
+
+use crate::config::LllPreviewEntry;
+use crate::preview::PreviewKey;
+use crate::unix;
+
+use crate::PREVIEW_T;
+
+const TAB_WIDTH: usize = 8;
+const POLL_INTERVAL: time::Duration = time::Duration::from_millis(10);
+
+pub struct PreviewRequest {
+    pub key: PreviewKey,
+    pub rows: usize,
+    pub max_preview_size: u64,
+    pub timeout: time::Duration,
+}
+
+pub struct PreviewResult {
+    pub key: PreviewKey,
+    pub output: std::io::Result<Vec<String>>,
+}
+
+enum PreviewOutcome {
+    Finished(std::io::Result<Vec<String>>),
+    Superseded(PreviewRequest),
+    Disconnected,
+}
+
+pub struct PreviewWorker {
+    requests: mpsc::Sender<PreviewRequest>,
+    results: mpsc::Receiver<PreviewResult>,
+    pending: Option<PreviewKey>,
+    shown: Option<PreviewKey>,
+}
+
+impl PreviewWorker {
+    pub fn new() -> Self {
+        let (request_tx, request_rx) = mpsc::channel();
+        let (result_tx, result_rx) = mpsc::channel();
+        thread::spawn(move || worker_loop(request_rx, result_tx));
+
+        PreviewWorker {
+            requests: request_tx,
+            results: result_rx,
+            pending: None,
+            shown: None,
+        }
+    }
+
+    pub fn request(&mut self, request: PreviewRequest) {
+        self.shown = None;
+        self.pending = Some(request.key.clone());
+        if self.requests.send(request).is_err() {
+            self.pending = None;
+        }
+    }
+
+    // results for cancelled requests are discarded once they arrive
+    pub fn cancel(&mut self) {
+        self.pending = None;
+        self.shown = None;
+    }
+
+    // for previews displayed without going through the worker
+    pub fn set_shown(&mut self, key: PreviewKey) {
+        self.pending = None;
+        self.shown = Some(key);
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    pub fn is_current(&self, key: &PreviewKey) -> bool {
+        match (self.pending.as_ref(), self.shown.as_ref()) {
+            (Some(k), _) | (None, Some(k)) => k == key,
+            (None, None) => false,
+        }
+    }
+
+    pub fn recv(&mut self) -> Option<PreviewResult> {
+        while let Ok(result) = self.results.try_recv() {
+            if self.pending.as_ref() == Some(&result.key) {
+                self.shown = self.pending.take();
+                return Some(result);
+            }
+        }
+        None
+    }
+}
+
+impl std::default::Default for PreviewWorker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn worker_loop(requests: mpsc::Receiver<PreviewRequest>, results: mpsc::Sender<PreviewResult>) {
+    let mut next = requests.recv().ok();
+    while let Some(mut request) = next.take() {
+        // only the most recent request is still of interest
+        while let Ok(s) = requests.try_recv() {
+            request = s;
+        }
+        match generate_preview(&request, &requests) {
+            PreviewOutcome::Finished(output) => {
+                let result = PreviewResult {
+                    key: request.key,
+                    output,
+                };
+                if results.send(result).is_err() {
+                    return;
+                }
+                next = requests.recv().ok();
+            }
+            PreviewOutcome::Superseded(s) => next = Some(s),
+            PreviewOutcome::Disconnected => return,
+        }
+    }
+}
+
+fn generate_preview(
+    request: &PreviewRequest,
+    requests: &mpsc::Receiver<PreviewRequest>,
+) -> PreviewOutcome {
+    let path = request.key.path.as_path();
+    match get_preview_entry(path) {
+        Some(entry) => run_program(entry, request, requests),
+        None => {
+            let output = preview_text(path, request.max_preview_size, request.rows);
+            PreviewOutcome::Finished(output.map(|s| format_output(&s, request)))
+        }
+    }
+}
+
+// extensions have priority over mimetypes, same as opening files
+fn get_preview_entry(path: &Path) -> Option<&'static LllPreviewEntry> {
+    if let Some(ext) = path.extension().and_then(|s| s.to_str()) {
+        if let Some(entry) = PREVIEW_T.extension.get(ext) {
+            return Some(entry);
+        }
+    }
+    if PREVIEW_T.mimetype.is_empty() {
+        return None;
+    }
+    let mimetype = unix::get_mimetype(path)?;
+    match PREVIEW_T.mimetype.get(mimetype.as_str()) {
+        Some(entry) => Some(entry),
+        None => {
+            // fall back to the top level type, ie. "image" for "image/png"
+            let top_level = mimetype.split('/').next()?;
+            PREVIEW_T.mimetype.get(top_level)
+        }
+    }
+}
+
+// runs the preview program while watching for newer requests and the timeout,
+// killing the program if either happens first
+fn run_program(
+    entry: &LllPreviewEntry,
+    request: &PreviewRequest,
+    requests: &mpsc::Receiver<PreviewRequest>,
+) -> PreviewOutcome {
+    let mut command = process::Command::new(&entry.program);
+    if let Some(args) = entry.args.as_ref() {
+        command.args(args);
+    }
+    command
+        .arg(&request.key.path)
+        .stdin(process::Stdio::null())
+        .stdout(process::Stdio::piped())
+        .stderr(process::Stdio::null());
+
+    let mut child = match command.spawn() {
+        Ok(s) => s,
+        Err(e) => return PreviewOutcome::Finished(Err(e)),
+    };
+
+    let (tx, rx) = mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        let max_preview_size = request.max_preview_size;
+        thread::spawn(move || {
+            let mut buf: Vec<u8> = Vec::new();
+            let res = stdout
+                .take(max_preview_size)
+                .read_to_end(&mut buf)
+                .map(|_| buf);
+            let _ = tx.send(res);
+        });
+    }
+
+    let start = time::Instant::now();
+    let outcome = loop {
+        match rx.recv_timeout(POLL_INTERVAL) {
+            Ok(res) => {
+                let output = res.map(|buf| format_output(&String::from_utf8_lossy(&buf), request));
+                break PreviewOutcome::Finished(output);
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                let err = std::io::Error::new(std::io::ErrorKind::Other, "no output");
+                break PreviewOutcome::Finished(Err(err));
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+        }
+        match requests.try_recv() {
+            Ok(s) => break PreviewOutcome::Superseded(s),
+            Err(mpsc::TryRecvError::Disconnected) => break PreviewOutcome::Disconnected,
+            Err(mpsc::TryRecvError::Empty) => {}
+        }
+        if start.elapsed() >= request.timeout {
+            let err = std::io::Error::new(std::io::ErrorKind::TimedOut, "preview timed out");
+            break PreviewOutcome::Finished(Err(err));
+        }
+    };
+    let _ = child.kill();
+    let _ = child.wait();
+    outcome
+}
+
+fn preview_text(path: &Path, max_size: u64, rows: usize) -> std::io::Result<String> {
+    // opening a fifo would block the worker until something writes to it
+    if !fs::metadata(path)?.is_file() {
+        return Ok(String::new());
+    }
+    let file = fs::File::open(path)?;
+    let mut reader = BufReader::new(file.take(max_size));
+
+    let mut buf: Vec<u8> = Vec::new();
+    for _ in 0..rows {
+        if reader.read_until(b'\n', &mut buf)? == 0 {
+            break;
+        }
+    }
+    // don't try to display binary files
+    if buf.contains(&0) {
+        return Ok(String::new());
+    }
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
+fn format_output(output: &str, request: &PreviewRequest) -> Vec<String> {
+    output
+        .lines()
+        .take(request.rows)
+        .map(|line| trim_to_width(line, request.key.cols))
+        .collect()
+}
+
+// expands tabs and strips control characters so lines never wrap
+fn trim_to_width(line: &str, width: usize) -> String {
+    let mut trimmed = String::with_capacity(width);
+    let mut total: usize = 0;
+    for ch in line.chars() {
+        let ch_width = if ch == '\t' {
+            TAB_WIDTH - total % TAB_WIDTH
+        } else if ch.is_control() {
+            continue;
+        } else {
+            unicode_width::UnicodeWidthChar::width(ch).unwrap_or(0)
+        };
+        if total + ch_width > width {
+            break;
+        }
+        if ch == '\t' {
+            trimmed.extend(std::iter::repeat(' ').take(ch_width));
+        } else {
+            trimmed.push(ch);
+        }
+        total += ch_width;
+    }
+    trimmed
+}
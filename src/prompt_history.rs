@@ -0,0 +1,227 @@
+use lazy_static::lazy_static;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::PROGRAM_NAME;
+
+const HISTORY_FILE: &str = "history.toml";
+// entries kept for each kind of prompt, the oldest go first
+const MAX_ENTRIES: usize = 100;
+
+lazy_static! {
+    static ref PROMPT_HISTORY: Mutex<LllPromptHistory> = Mutex::new(LllPromptHistory::default());
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PromptKind {
+    Console,
+    Search,
+    Rename,
+    OpenWith,
+    SetMode,
+    Help,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct LllPromptHistory {
+    #[serde(default)]
+    console: VecDeque<String>,
+    #[serde(default)]
+    search: VecDeque<String>,
+    #[serde(default)]
+    rename: VecDeque<String>,
+    #[serde(default)]
+    open_with: VecDeque<String>,
+    #[serde(default)]
+    set_mode: VecDeque<String>,
+    #[serde(default)]
+    help: VecDeque<String>,
+}
+
+impl LllPromptHistory {
+    fn entries_ref(&self, kind: PromptKind) -> &VecDeque<String> {
+        match kind {
+            PromptKind::Console => &self.console,
+            PromptKind::Search => &self.search,
+            PromptKind::Rename => &self.rename,
+            PromptKind::OpenWith => &self.open_with,
+            PromptKind::SetMode => &self.set_mode,
+            PromptKind::Help => &self.help,
+        }
+    }
+
+    fn entries_mut(&mut self, kind: PromptKind) -> &mut VecDeque<String> {
+        match kind {
+            PromptKind::Console => &mut self.console,
+            PromptKind::Search => &mut self.search,
+            PromptKind::Rename => &mut self.rename,
+            PromptKind::OpenWith => &mut self.open_with,
+            PromptKind::SetMode => &mut self.set_mode,
+            PromptKind::Help => &mut self.help,
+        }
+    }
+
+    // a line entered again moves to the end instead of showing up twice
+    pub fn push(&mut self, kind: PromptKind, line: &str) {
+        let entries = self.entries_mut(kind);
+        entries.retain(|s| s != line);
+        entries.push_back(String::from(line));
+        while entries.len() > MAX_ENTRIES {
+            entries.pop_front();
+        }
+    }
+}
+
+// walks through the history of a prompt, only stopping at entries that start
+// with what was before the cursor on the first step, like a shell does
+pub struct HistoryCursor {
+    entries: Vec<String>,
+    index: Option<usize>,
+    typed: String,
+    filter: String,
+}
+
+impl HistoryCursor {
+    pub fn new(kind: PromptKind) -> Self {
+        let entries = PROMPT_HISTORY
+            .lock()
+            .unwrap()
+            .entries_ref(kind)
+            .iter()
+            .cloned()
+            .collect();
+        Self::with_entries(entries)
+    }
+
+    fn with_entries(entries: Vec<String>) -> Self {
+        HistoryCursor {
+            entries,
+            index: None,
+            typed: String::new(),
+            filter: String::new(),
+        }
+    }
+
+    pub fn older(&mut self, line: &str, pos: usize) -> Option<&str> {
+        let end = match self.index {
+            Some(i) => i,
+            None => {
+                self.typed = String::from(line);
+                self.filter = String::from(&line[..pos]);
+                self.entries.len()
+            }
+        };
+        let filter = &self.filter;
+        let found = self.entries[..end]
+            .iter()
+            .rposition(|s| s.starts_with(filter.as_str()) && s != line)?;
+        self.index = Some(found);
+        Some(&self.entries[found])
+    }
+
+    // past the newest entry, gives back what had been typed
+    pub fn newer(&mut self, line: &str) -> Option<&str> {
+        let start = self.index? + 1;
+        let filter = &self.filter;
+        match self.entries[start..]
+            .iter()
+            .position(|s| s.starts_with(filter.as_str()) && s != line)
+        {
+            Some(i) => {
+                self.index = Some(start + i);
+                Some(&self.entries[start + i])
+            }
+            None => {
+                self.index = None;
+                Some(&self.typed)
+            }
+        }
+    }
+
+    // editing the line starts a new search from it
+    pub fn reset(&mut self) {
+        self.index = None;
+    }
+}
+
+pub fn push(kind: PromptKind, line: &str) -> std::io::Result<()> {
+    let mut history = PROMPT_HISTORY.lock().unwrap();
+    history.push(kind, line);
+    save(&history)
+}
+
+pub fn load() -> std::io::Result<()> {
+    let contents = match fs::read_to_string(history_file()?) {
+        Ok(s) => s,
+        Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    match toml::from_str(&contents) {
+        Ok(s) => {
+            *PROMPT_HISTORY.lock().unwrap() = s;
+            Ok(())
+        }
+        Err(e) => Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Error parsing {}: {}", HISTORY_FILE, e),
+        )),
+    }
+}
+
+fn save(history: &LllPromptHistory) -> std::io::Result<()> {
+    let contents = match toml::to_string(history) {
+        Ok(s) => s,
+        Err(e) => return Err(std::io::Error::new(std::io::ErrorKind::Other, e)),
+    };
+    let file_path = history_file()?;
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(file_path, contents)
+}
+
+// $XDG_DATA_HOME/lll/history.toml
+fn history_file() -> std::io::Result<PathBuf> {
+    match xdg::BaseDirectories::with_prefix(PROGRAM_NAME) {
+        Ok(dirs) => Ok(dirs.get_data_home().join(HISTORY_FILE)),
+        Err(e) => Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            e.to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_moves_duplicates() {
+        let mut history = LllPromptHistory::default();
+        history.push(PromptKind::Console, "cd /");
+        history.push(PromptKind::Console, "mkdir a");
+        history.push(PromptKind::Console, "cd /");
+        history.push(PromptKind::Search, "search a");
+        assert_eq!(history.console, vec!["mkdir a", "cd /"]);
+        assert_eq!(history.search, vec!["search a"]);
+    }
+
+    #[test]
+    fn cursor_filters_by_prefix() {
+        let entries = vec!["cd /tmp", "mkdir a", "cd /usr", "cd /"];
+        let mut cursor =
+            HistoryCursor::with_entries(entries.iter().map(|s| String::from(*s)).collect());
+
+        assert_eq!(cursor.older("cd /u", 5), Some("cd /usr"));
+        assert_eq!(cursor.older("cd /usr", 7), None);
+        assert_eq!(cursor.newer("cd /usr"), Some("cd /u"));
+
+        assert_eq!(cursor.older("cd /u", 2), Some("cd /"));
+        assert_eq!(cursor.older("cd /", 4), Some("cd /usr"));
+        assert_eq!(cursor.older("cd /usr", 7), Some("cd /tmp"));
+        assert_eq!(cursor.newer("cd /tmp"), Some("cd /usr"));
+    }
+}
@@ -1,14 +1,29 @@
+use std::path::Path;
 use std::process;
 use std::time;
 
-use crate::commands::{CommandKeybind, FileOperationThread, LllCommand, ReloadDirList};
+use crate::commands::{
+    self, ChooseFiles, CommandKeybind, FileOperationThread, LllCommand, ReloadDirList, Shell,
+};
 use crate::config::{self, LllCommandMapping, LllConfig};
 use crate::context::LllContext;
 use crate::error::LllError;
+use crate::macros;
+use crate::preview;
+use crate::prompt_history;
+use crate::session::{self, LllSession};
 use crate::tab::LllTab;
 use crate::ui;
 use crate::window::LllPanel;
 use crate::window::LllView;
+use crate::Args;
+
+// how often to check on previews being generated, in milliseconds
+const PREVIEW_POLL_TIMEOUT: i32 = 20;
+// how often to check on background shell commands, in milliseconds
+const JOB_POLL_TIMEOUT: i32 = 500;
+// counts stop growing here, as in vi, so commands don't loop for ages
+const MAX_COUNT: usize = 9999;
 
 fn recurse_get_keycommand(keymap: &LllCommandMapping) -> Option<&dyn LllCommand> {
     let (term_rows, term_cols) = ui::getmaxyx();
@@ -32,7 +47,7 @@ fn recurse_get_keycommand(keymap: &LllCommandMapping) -> Option<&dyn LllCommand>
         ui::display_menu(&win, &display_vec);
         ncurses::doupdate();
 
-        ncurses::wgetch(win.win)
+        macros::wget_wch(win.win).unwrap_or(config::keymap::ESCAPE)
     };
     ncurses::doupdate();
 
@@ -51,7 +66,7 @@ fn reload_tab(index: usize, context: &mut LllContext, view: &LllView) -> std::io
     ReloadDirList::reload(index, context)?;
     if index == context.curr_tab_index {
         let dirty_tab = &mut context.tabs[index];
-        dirty_tab.refresh(view, &context.config_t);
+        dirty_tab.refresh(view, &context.config_t, &context.usercache);
     }
     Ok(())
 }
@@ -71,12 +86,13 @@ fn join_thread(
             view.bot_win.queue_for_refresh();
         }
         Ok(_) => {
-            if tab_src < context.tabs.len() {
+            if let Some(tab_src) = tab_src {
                 reload_tab(tab_src, context, view)?;
             }
-            if tab_dest != tab_src && tab_dest < context.tabs.len() {
+            if let Some(tab_dest) = tab_dest.filter(|i| Some(*i) != tab_src) {
                 reload_tab(tab_dest, context, view)?;
             }
+            preview::preview_curr_entry(context, view);
         }
     }
     Ok(())
@@ -102,49 +118,175 @@ fn process_threads(context: &mut LllContext, view: &LllView) -> std::io::Result<
     Ok(())
 }
 
+// reports background shell commands as they finish
+fn process_jobs(context: &mut LllContext, view: &LllView) -> std::io::Result<()> {
+    let mut i = 0;
+    while i < context.jobs.len() {
+        match context.jobs[i].child.try_wait()? {
+            Some(status) => {
+                let job = context.jobs.swap_remove(i);
+                Shell::reload_tabs(context, view)?;
+                let msg = format!("[{}] {}", status, job.command);
+                if status.success() {
+                    ui::wprint_msg(&view.bot_win, &msg);
+                } else {
+                    ui::wprint_err(&view.bot_win, &msg);
+                }
+                ncurses::doupdate();
+            }
+            None => i += 1,
+        }
+    }
+    Ok(())
+}
+
+// the tab bar grows and shrinks with the number of tabs and their names
+fn update_tab_bar(context: &LllContext, view: &mut LllView) {
+    let tab_width = ui::tab_bar_width(context);
+    if tab_width != view.tab_width {
+        view.resize_tab_win(tab_width);
+        context.curr_tab_ref().refresh_path_status(&view.top_win);
+    }
+    ui::redraw_tab_view(&view.tab_win, context);
+}
+
 #[inline]
 fn resize_handler(context: &mut LllContext, view: &LllView) {
     ui::redraw_tab_view(&view.tab_win, &context);
+    context.preview.cancel();
 
     let curr_tab = &mut context.tabs[context.curr_tab_index];
-    curr_tab.refresh(view, &context.config_t);
+    curr_tab.refresh(view, &context.config_t, &context.usercache);
+    preview::preview_curr_entry(context, view);
     ncurses::doupdate();
 }
 
-fn init_context(context: &mut LllContext, view: &LllView) {
-    match std::env::current_dir() {
-        Ok(curr_path) => match LllTab::new(curr_path, &context.config_t.sort_option) {
+// a directory opens as is, a file opens its directory with the file under the cursor
+fn open_path_tab(context: &mut LllContext, path: &Path) -> std::io::Result<()> {
+    let path = path.canonicalize()?;
+    let sort_option = &context.config_t.sort_option;
+
+    let tab = if path.is_dir() {
+        LllTab::new(path, sort_option)?
+    } else {
+        let parent = match path.parent() {
+            Some(s) => s.to_path_buf(),
+            None => path.clone(),
+        };
+        let mut tab = LllTab::new(parent, sort_option)?;
+        let curr_list = &mut tab.curr_list;
+        if let Some(i) = curr_list
+            .contents
+            .iter()
+            .position(|entry| entry.file_path() == &path)
+        {
+            curr_list.index = Some(i);
+        }
+        tab
+    };
+    context.tabs.push(tab);
+    Ok(())
+}
+
+fn init_tabs(context: &mut LllContext, args: &Args) {
+    if args.restore_session {
+        match LllSession::read() {
+            Ok(s) => s.restore(context),
+            Err(e) => eprintln!("Failed to restore session: {}", e),
+        }
+    }
+
+    let first_path_tab = context.tabs.len();
+    for path in args.paths.iter() {
+        if let Err(e) = open_path_tab(context, path) {
+            eprintln!("{}: {}", path.to_string_lossy(), e);
+        }
+    }
+    if first_path_tab < context.tabs.len() {
+        context.curr_tab_index = first_path_tab;
+    }
+
+    if context.tabs.is_empty() {
+        let tab = std::env::current_dir()
+            .and_then(|curr_path| LllTab::new(curr_path, &context.config_t.sort_option));
+        match tab {
             Ok(tab) => {
                 context.tabs.push(tab);
-                context.curr_tab_index = context.tabs.len() - 1;
-
-                ui::redraw_tab_view(&view.tab_win, &context);
-                let curr_tab = &mut context.tabs[context.curr_tab_index];
-                curr_tab.refresh(view, &context.config_t);
-                ncurses::doupdate();
+                context.curr_tab_index = 0;
             }
             Err(e) => {
-                ui::end_ncurses();
                 eprintln!("{}", e);
                 process::exit(1);
             }
-        },
-        Err(e) => {
-            ui::end_ncurses();
-            eprintln!("{}", e);
-            process::exit(1);
         }
     }
+
+    if let Err(e) = std::env::set_current_dir(&context.curr_tab_ref().curr_path) {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
 }
 
-pub fn run(config_t: LllConfig, keymap_t: LllCommandMapping) {
-    ui::init_ncurses();
+// digits start a count unless bound themselves, 0 only ever continues one
+fn count_digit(ch: i32, count: Option<usize>, keymap_t: &LllCommandMapping) -> Option<usize> {
+    if ch < '0' as i32 || ch > '9' as i32 {
+        return None;
+    }
+    if count.is_none() && (ch == '0' as i32 || keymap_t.contains_key(&ch)) {
+        return None;
+    }
+    Some((ch - '0' as i32) as usize)
+}
+
+// puts the file status back where the pending count was shown
+fn refresh_count(context: &LllContext, view: &LllView) {
+    context.curr_tab_ref().refresh_file_status(
+        &view.bot_win,
+        &context.config_t,
+        &context.usercache,
+    );
+    ncurses::doupdate();
+}
+
+fn init_context(context: &mut LllContext, view: &LllView) {
+    ui::redraw_tab_view(&view.tab_win, &context);
+    let curr_tab = &mut context.tabs[context.curr_tab_index];
+    curr_tab.refresh(view, &context.config_t, &context.usercache);
+    preview::preview_curr_entry(context, view);
+    ncurses::doupdate();
+}
 
+pub fn run(config_t: LllConfig, keymap_t: LllCommandMapping, args: &Args) {
     let mut context = LllContext::new(config_t);
-    let mut view = LllView::new(context.config_t.column_ratio);
+    context.keybindings = commands::keybindings(&keymap_t);
+    init_tabs(&mut context, args);
+    if context.config_t.save_macros {
+        if let Err(e) = macros::load() {
+            eprintln!("{}", e);
+        }
+    }
+    if let Err(e) = prompt_history::load() {
+        eprintln!("{}", e);
+    }
+
+    ui::init_ncurses();
+
+    let mut view = LllView::new(context.config_t.column_ratio, 0);
     init_context(&mut context, &view);
+    update_tab_bar(&context, &mut view);
+    ncurses::doupdate();
+
+    let mut last_session = String::new();
+    // a count typed before a key binding, as in 5j or 3dd
+    let mut count: Option<usize> = None;
 
     while !context.exit {
+        preview::process_preview(&mut context, &view);
+        if let Err(e) = process_jobs(&mut context, &view) {
+            ui::wprint_err(&view.bot_win, e.to_string().as_str());
+            ncurses::doupdate();
+        }
+
         if !context.threads.is_empty() {
             ncurses::timeout(0);
             match process_threads(&mut context, &view) {
@@ -152,22 +294,37 @@ pub fn run(config_t: LllConfig, keymap_t: LllCommandMapping) {
                 Err(e) => ui::wprint_err(&view.bot_win, e.to_string().as_str()),
             }
             ncurses::doupdate();
+        } else if context.preview.is_pending() {
+            ncurses::timeout(PREVIEW_POLL_TIMEOUT);
+        } else if !context.jobs.is_empty() {
+            ncurses::timeout(JOB_POLL_TIMEOUT);
         } else {
             ncurses::timeout(-1);
         }
 
-        if let Some(ch) = ncurses::get_wch() {
-            let ch = match ch {
-                ncurses::WchResult::Char(s) => s as i32,
-                ncurses::WchResult::KeyCode(s) => s,
-            };
-
+        if count.is_none() {
+            macros::start_command();
+        }
+        if let Some(ch) = macros::get_wch() {
             if ch == ncurses::KEY_RESIZE {
                 view.resize_views();
                 resize_handler(&mut context, &view);
                 continue;
             }
 
+            if count.is_some() && ch == config::keymap::ESCAPE {
+                count = None;
+                refresh_count(&context, &view);
+                continue;
+            }
+            if let Some(digit) = count_digit(ch, count, &keymap_t) {
+                let n = (count.unwrap_or(0) * 10 + digit).min(MAX_COUNT);
+                count = Some(n);
+                ui::wprint_msg(&view.bot_win, &n.to_string());
+                ncurses::doupdate();
+                continue;
+            }
+
             let keycommand;
 
             match keymap_t.get(&ch) {
@@ -182,11 +339,20 @@ pub fn run(config_t: LllConfig, keymap_t: LllCommandMapping) {
                     // TODO: remove this eventually
                     ui::wprint_err(&view.bot_win, &format!("Unknown keycode: {}", ch));
                     ncurses::doupdate();
+                    count = None;
                     continue;
                 }
             }
-            match keycommand.execute(&mut context, &view) {
-                Ok(()) => {}
+            if count.is_some() {
+                refresh_count(&context, &view);
+            }
+            let count = count.take();
+            match keycommand.execute(&mut context, &view, count) {
+                Ok(()) => {
+                    if keycommand.repeatable() {
+                        context.last_command = Some((keycommand.clone_box(), count));
+                    }
+                }
                 Err(LllError::IO(e)) => {
                     ui::wprint_err(&view.bot_win, e.to_string().as_str());
                     ncurses::doupdate();
@@ -196,7 +362,24 @@ pub fn run(config_t: LllConfig, keymap_t: LllCommandMapping) {
                     ncurses::doupdate();
                 }
             }
+            if context.config_t.save_session {
+                if let Err(e) = session::save_session(&context, &mut last_session) {
+                    ui::wprint_err(&view.bot_win, e.to_string().as_str());
+                }
+            }
+            if !context.exit {
+                update_tab_bar(&context, &mut view);
+                preview::preview_curr_entry(&mut context, &view);
+                ncurses::doupdate();
+            }
         }
     }
     ui::end_ncurses();
+
+    if let Some(paths) = context.chosen_files.as_ref() {
+        if let Err(e) = ChooseFiles::write_chosen_files(paths) {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
 }
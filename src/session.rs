@@ -0,0 +1,147 @@
+use serde_derive::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::context::LllContext;
+use crate::sort;
+use crate::tab::LllTab;
+
+use crate::{HOME_DIR, PROGRAM_NAME};
+
+const SESSION_FILE: &str = "session.toml";
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct LllSessionTab {
+    path: PathBuf,
+    cursor: Option<String>,
+    #[serde(default)]
+    selected: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LllSession {
+    curr_tab_index: usize,
+    sort_option: sort::SortOption,
+    tabs: Vec<LllSessionTab>,
+}
+
+impl LllSession {
+    // toml strings can't hold paths that aren't utf-8, so those tabs are left out
+    pub fn from_context(context: &LllContext) -> Self {
+        let mut curr_tab_index = 0;
+        let mut tabs = Vec::with_capacity(context.tabs.len());
+        for (i, tab) in context.tabs.iter().enumerate() {
+            if tab.curr_path.to_str().is_none() {
+                continue;
+            }
+            if i <= context.curr_tab_index {
+                curr_tab_index = tabs.len();
+            }
+            tabs.push(LllSessionTab {
+                path: tab.curr_path.clone(),
+                cursor: tab
+                    .curr_list
+                    .get_curr_ref()
+                    .map(|entry| String::from(entry.file_name())),
+                selected: tab
+                    .curr_list
+                    .selected_entries()
+                    .map(|entry| String::from(entry.file_name()))
+                    .collect(),
+            });
+        }
+
+        LllSession {
+            curr_tab_index,
+            sort_option: context.config_t.sort_option.clone(),
+            tabs,
+        }
+    }
+
+    pub fn read() -> std::io::Result<Self> {
+        let contents = fs::read_to_string(state_file(SESSION_FILE)?)?;
+        match toml::from_str(&contents) {
+            Ok(s) => Ok(s),
+            Err(e) => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Error parsing {}: {}", SESSION_FILE, e),
+            )),
+        }
+    }
+
+    // tabs whose directories have gone away are dropped. the sort option the
+    // session was saved with replaces the one from lll.toml
+    pub fn restore(self, context: &mut LllContext) {
+        context.config_t.sort_option = self.sort_option;
+        let sort_option = &context.config_t.sort_option;
+
+        for saved in self.tabs {
+            let mut tab = match LllTab::new(saved.path, sort_option) {
+                Ok(s) => s,
+                Err(_) => continue,
+            };
+            let curr_list = &mut tab.curr_list;
+            for entry in curr_list.contents.iter_mut() {
+                if saved.selected.iter().any(|name| name == entry.file_name()) {
+                    entry.set_selected(true);
+                }
+            }
+            if let Some(cursor) = saved.cursor {
+                if let Some(i) = curr_list
+                    .contents
+                    .iter()
+                    .position(|entry| entry.file_name() == cursor)
+                {
+                    curr_list.index = Some(i);
+                }
+            }
+            context.tabs.push(tab);
+        }
+
+        if self.curr_tab_index < context.tabs.len() {
+            context.curr_tab_index = self.curr_tab_index;
+        } else {
+            context.curr_tab_index = context.tabs.len().saturating_sub(1);
+        }
+    }
+}
+
+// writes the session out only when it differs from what was last written
+pub fn save_session(context: &LllContext, last_saved: &mut String) -> std::io::Result<()> {
+    let contents = match toml::to_string(&LllSession::from_context(context)) {
+        Ok(s) => s,
+        Err(e) => return Err(std::io::Error::new(std::io::ErrorKind::Other, e)),
+    };
+    if contents == *last_saved {
+        return Ok(());
+    }
+
+    let file_path = state_file(SESSION_FILE)?;
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // written next to the session and renamed over it, so a crash midway
+    // never leaves a truncated session behind
+    let tmp_path = file_path.with_extension("toml.tmp");
+    fs::write(&tmp_path, &contents)?;
+    fs::rename(&tmp_path, &file_path)?;
+    *last_saved = contents;
+    Ok(())
+}
+
+// $XDG_STATE_HOME/lll/<file_name>, falling back to ~/.local/state
+pub fn state_file(file_name: &str) -> std::io::Result<PathBuf> {
+    let state_home = match std::env::var_os("XDG_STATE_HOME") {
+        Some(s) if !s.is_empty() => PathBuf::from(s),
+        _ => match HOME_DIR.as_ref() {
+            Some(s) => s.join(".local").join("state"),
+            None => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "Cannot find home directory",
+                ));
+            }
+        },
+    };
+    Ok(state_home.join(PROGRAM_NAME).join(file_name))
+}
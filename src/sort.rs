@@ -5,9 +5,9 @@ use std::time;
 use crate::fs::LllDirEntry;
 
 use alphanumeric_sort::compare_str;
-use serde_derive::Deserialize;
+use serde_derive::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum SortType {
     Lexical,
     Mtime,
@@ -25,7 +25,7 @@ impl SortType {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SortOption {
     pub show_hidden: bool,
     pub directories_first: bool,
@@ -0,0 +1,207 @@
+use std::fs;
+
+use users::UsersCache;
+
+use crate::fs::{LllDirEntry, LllDirList};
+use crate::ui;
+use crate::unix;
+
+pub const DEFAULT_STATUS_FORMAT: &str =
+    "{mode}  {owner} {group}  {size}  {mtime}  {link}  {position}  {selected}  {free}";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StatusField {
+    Mode,
+    Owner,
+    Group,
+    Size,
+    Mtime,
+    Link,
+    Position,
+    Selected,
+    Free,
+}
+
+impl StatusField {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "mode" => Some(StatusField::Mode),
+            "owner" => Some(StatusField::Owner),
+            "group" => Some(StatusField::Group),
+            "size" => Some(StatusField::Size),
+            "mtime" => Some(StatusField::Mtime),
+            "link" => Some(StatusField::Link),
+            "position" => Some(StatusField::Position),
+            "selected" => Some(StatusField::Selected),
+            "free" => Some(StatusField::Free),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum StatusToken {
+    Text(String),
+    Field(StatusField),
+}
+
+#[derive(Clone, Debug)]
+pub struct LllStatusFormat {
+    tokens: Vec<StatusToken>,
+}
+
+impl LllStatusFormat {
+    // fields are written as {name}, a literal brace is written as {{ or }}
+    pub fn parse(format: &str) -> Result<Self, String> {
+        let mut tokens = Vec::new();
+        let mut text = String::new();
+        let mut chars = format.chars().peekable();
+
+        while let Some(ch) = chars.next() {
+            match ch {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(format!("Unclosed field: {{{}", name)),
+                        }
+                    }
+                    let field = match StatusField::parse(name.trim()) {
+                        Some(s) => s,
+                        None => return Err(format!("Unknown status field: {}", name)),
+                    };
+                    if !text.is_empty() {
+                        tokens.push(StatusToken::Text(text.split_off(0)));
+                    }
+                    tokens.push(StatusToken::Field(field));
+                }
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            tokens.push(StatusToken::Text(text));
+        }
+        Ok(LllStatusFormat { tokens })
+    }
+
+    pub fn render(&self, dirlist: &LllDirList, usercache: &UsersCache) -> String {
+        let index = match dirlist.index {
+            Some(s) => s,
+            None => return String::new(),
+        };
+        let entry = &dirlist.contents[index];
+
+        let mut status = String::new();
+        // fields with nothing to show also swallow the spacing after them
+        let mut skip_space = false;
+        for token in &self.tokens {
+            match token {
+                StatusToken::Text(s) if skip_space => status.push_str(s.trim_start()),
+                StatusToken::Text(s) => status.push_str(s),
+                StatusToken::Field(field) => {
+                    let value = render_field(*field, dirlist, index, entry, usercache);
+                    skip_space = value.is_empty();
+                    status.push_str(&value);
+                    continue;
+                }
+            }
+            skip_space = false;
+        }
+        status
+    }
+}
+
+impl std::default::Default for LllStatusFormat {
+    fn default() -> Self {
+        LllStatusFormat::parse(DEFAULT_STATUS_FORMAT).unwrap()
+    }
+}
+
+fn render_field(
+    field: StatusField,
+    dirlist: &LllDirList,
+    index: usize,
+    entry: &LllDirEntry,
+    usercache: &UsersCache,
+) -> String {
+    use std::os::unix::fs::PermissionsExt;
+
+    match field {
+        StatusField::Mode => unix::stringify_mode(entry.metadata.permissions.mode()),
+        StatusField::Owner => unix::user_name(usercache, entry.metadata.uid),
+        StatusField::Group => unix::group_name(usercache, entry.metadata.gid),
+        StatusField::Size => ui::file_size_to_string(entry.metadata.len),
+        StatusField::Mtime => unix::format_time(&entry.metadata.modified, ui::TIME_FORMAT),
+        StatusField::Link => {
+            if !entry.metadata.file_type.is_symlink() {
+                return String::new();
+            }
+            match fs::read_link(entry.file_path()) {
+                Ok(path) => format!("-> {}", path.to_string_lossy()),
+                Err(_) => String::new(),
+            }
+        }
+        StatusField::Position => format!("{}/{}", index + 1, dirlist.contents.len()),
+        StatusField::Selected => match dirlist.selected_entries().count() {
+            0 => String::new(),
+            n => format!("{} selected", n),
+        },
+        StatusField::Free => match unix::free_space(dirlist.file_path()) {
+            Some(s) => format!("{} free", ui::file_size_to_string(s)),
+            None => String::new(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_fields_and_text() {
+        let format = LllStatusFormat::parse("{mode} [{size}]").unwrap();
+        assert_eq!(
+            format.tokens,
+            vec![
+                StatusToken::Field(StatusField::Mode),
+                StatusToken::Text(String::from(" [")),
+                StatusToken::Field(StatusField::Size),
+                StatusToken::Text(String::from("]")),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_escaped_braces() {
+        let format = LllStatusFormat::parse("{{{position}}}").unwrap();
+        assert_eq!(
+            format.tokens,
+            vec![
+                StatusToken::Text(String::from("{")),
+                StatusToken::Field(StatusField::Position),
+                StatusToken::Text(String::from("}")),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(LllStatusFormat::parse("{mode").is_err());
+        assert!(LllStatusFormat::parse("{colour}").is_err());
+    }
+
+    #[test]
+    fn default_format_parses() {
+        assert!(LllStatusFormat::parse(DEFAULT_STATUS_FORMAT).is_ok());
+    }
+}
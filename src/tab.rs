@@ -1,5 +1,7 @@
 use std::path::PathBuf;
 
+use users::UsersCache;
+
 use crate::fs::LllDirList;
 use crate::history::{DirectoryHistory, LllHistory};
 use crate::sort;
@@ -9,6 +11,7 @@ use crate::LllConfig;
 
 use crate::THEME_T;
 
+#[derive(Clone)]
 pub struct LllTab {
     pub history: LllHistory,
     pub curr_path: PathBuf,
@@ -30,28 +33,71 @@ impl LllTab {
         Ok(tab)
     }
 
-    pub fn refresh(&mut self, views: &LllView, config_t: &LllConfig) {
-        self.refresh_curr(&views.mid_win, config_t);
+    pub fn refresh(&mut self, views: &LllView, config_t: &LllConfig, usercache: &UsersCache) {
+        self.refresh_parent(&views.left_win, config_t, usercache);
+        self.refresh_curr(&views.mid_win, config_t, usercache);
         self.refresh_path_status(&views.top_win);
-        self.refresh_file_status(&views.bot_win);
+        self.refresh_file_status(&views.bot_win, config_t, usercache);
     }
 
-    pub fn refresh_curr(&mut self, win: &LllPanel, config_t: &LllConfig) {
+    pub fn refresh_curr(&mut self, win: &LllPanel, config_t: &LllConfig, usercache: &UsersCache) {
         ui::display_contents(
             win,
             &mut self.curr_list,
             config_t,
+            usercache,
             &ui::PRIMARY_DISPLAY_OPTION,
         );
     }
 
-    pub fn refresh_file_status(&self, win: &LllPanel) {
+    pub fn refresh_parent(&mut self, win: &LllPanel, config_t: &LllConfig, usercache: &UsersCache) {
+        let parent = match self.curr_path.parent() {
+            Some(s) => s.to_path_buf(),
+            None => {
+                ncurses::werase(win.win);
+                win.queue_for_refresh();
+                return;
+            }
+        };
+        let sort_option = &config_t.sort_option;
+        match self.history.get_mut_or_create(&parent, sort_option) {
+            Ok(dirlist) => {
+                if dirlist.need_update() {
+                    if let Err(e) = dirlist.update_contents(sort_option) {
+                        ui::wprint_err(win, e.to_string().as_str());
+                        return;
+                    }
+                }
+                // keep the parent's cursor on the directory we are in
+                let curr_path = &self.curr_path;
+                if let Some(i) = dirlist
+                    .contents
+                    .iter()
+                    .position(|entry| entry.file_path() == curr_path)
+                {
+                    dirlist.index = Some(i);
+                }
+                ui::display_contents(
+                    win,
+                    dirlist,
+                    config_t,
+                    usercache,
+                    &ui::SECONDARY_DISPLAY_OPTION,
+                );
+            }
+            Err(e) => ui::wprint_err(win, e.to_string().as_str()),
+        }
+    }
+
+    pub fn refresh_file_status(
+        &self,
+        win: &LllPanel,
+        config_t: &LllConfig,
+        usercache: &UsersCache,
+    ) {
         ncurses::werase(win.win);
         ncurses::wmove(win.win, 0, 0);
-        if let Some(index) = self.curr_list.index {
-            let entry = &self.curr_list.contents[index];
-            ui::wprint_file_status(win, entry);
-        }
+        ui::wprint_file_status(win, &self.curr_list, &config_t.status_format, usercache);
     }
 
     pub fn refresh_path_status(&self, win: &LllPanel) {
@@ -1,34 +1,174 @@
-use crate::window;
+use crate::completion::LllCompleter;
+use crate::macros;
+use crate::prompt_history::{self, HistoryCursor, PromptKind};
+use crate::ui;
+use crate::window::{self, LllPageState};
 use crate::KEYMAP_T;
 
-use std::unimplemented;
-
-use rustyline::completion::{Candidate, Completer, FilenameCompleter, Pair};
+use rustyline::completion::{Candidate, Pair};
 use rustyline::line_buffer;
 
+// candidates shown at once in the completion menu
+const MAX_MENU_ROWS: usize = 10;
+// how long to wait after escape for the key an alt combination sends with it
+const ALT_KEY_DELAY: i32 = 25;
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric()
+}
+
+fn is_not_whitespace(ch: char) -> bool {
+    !ch.is_whitespace()
+}
+
+// the start of the word before pos, skipping anything between them
+fn prev_word(line: &str, pos: usize, is_word: fn(char) -> bool) -> usize {
+    let mut start = pos;
+    let mut in_word = false;
+    for (i, ch) in line[..pos].char_indices().rev() {
+        if is_word(ch) {
+            in_word = true;
+        } else if in_word {
+            break;
+        }
+        start = i;
+    }
+    start
+}
+
+// the end of the word after pos
+fn next_word(line: &str, pos: usize, is_word: fn(char) -> bool) -> usize {
+    let mut in_word = false;
+    for (i, ch) in line[pos..].char_indices() {
+        if is_word(ch) {
+            in_word = true;
+        } else if in_word {
+            return pos + i;
+        }
+    }
+    line.len()
+}
+
+// the first column shown, moved only as far as needed to keep the
+// cursor inside a field this wide
+fn scroll_to(scroll: usize, cursor: usize, width: usize) -> usize {
+    if cursor < scroll {
+        cursor
+    } else if cursor >= scroll + width {
+        cursor + 1 - width
+    } else {
+        scroll
+    }
+}
+
+// the part of line that falls in the columns from scroll on
+fn visible_slice(line: &str, scroll: usize, width: usize) -> &str {
+    let mut col = 0;
+    let mut start = line.len();
+    let mut end = line.len();
+    for (i, ch) in line.char_indices() {
+        if col >= scroll && start == line.len() {
+            start = i;
+        }
+        col += unicode_width::UnicodeWidthChar::width(ch).unwrap_or(0);
+        if col > scroll + width {
+            end = i;
+            break;
+        }
+    }
+    &line[start.min(end)..end]
+}
+
+fn cursor_col(line_buffer: &line_buffer::LineBuffer) -> usize {
+    unicode_width::UnicodeWidthStr::width(&line_buffer.as_str()[..line_buffer.pos()])
+}
+
+// removes a range of the line, giving back what was removed
+fn kill_range(line_buffer: &mut line_buffer::LineBuffer, range: std::ops::Range<usize>) -> String {
+    let mut line = String::from(line_buffer.as_str());
+    let killed: String = line.drain(range.clone()).collect();
+    line_buffer.update(&line, range.start);
+    killed
+}
+
 struct CompletionTracker {
     pub index: usize,
     pub pos: usize,
+    // the line and cursor before completing, put back on escape
     pub original: String,
+    pub original_pos: usize,
     pub candidates: Vec<Pair>,
 }
 
 impl CompletionTracker {
-    pub fn new(pos: usize, candidates: Vec<Pair>, original: String) -> Self {
+    pub fn new(pos: usize, candidates: Vec<Pair>, original: String, original_pos: usize) -> Self {
         CompletionTracker {
             index: 0,
             pos,
             original,
+            original_pos,
             candidates,
         }
     }
 }
 
+// lists the candidates above the prompt, scrolling to keep the current one in view
+struct CompletionMenu {
+    win: window::LllPanel,
+    page_state: LllPageState,
+}
+
+impl CompletionMenu {
+    fn new(candidates: usize, prompt_row: usize, cols: i32) -> Option<Self> {
+        // the first row is taken by the separator line
+        let rows = candidates
+            .min(MAX_MENU_ROWS)
+            .min(prompt_row.saturating_sub(1));
+        if rows == 0 {
+            return None;
+        }
+        let win = window::LllPanel::new(rows as i32 + 1, cols, (prompt_row - rows - 1, 0));
+        win.move_to_top();
+        Some(CompletionMenu {
+            win,
+            page_state: LllPageState::new(),
+        })
+    }
+
+    fn draw(&mut self, tracker: &CompletionTracker) {
+        let len = tracker.candidates.len();
+        let rows = self.win.rows - 1;
+        self.page_state
+            .update_page_state(tracker.index, rows, len, 1);
+
+        let (start, end) = (self.page_state.start, self.page_state.end);
+        let display_vec: Vec<String> = tracker.candidates[start..end]
+            .iter()
+            .map(|candidate| format!("  {}", candidate.display()))
+            .collect();
+        ui::display_menu(&self.win, &display_vec);
+
+        let count = format!(" {}/{} ", tracker.index + 1, len);
+        let count_col = (self.win.cols - count.len() as i32 - 1).max(0);
+        ncurses::mvwaddstr(self.win.win, 0, count_col, &count);
+        ncurses::mvwchgat(
+            self.win.win,
+            (tracker.index - start + 1) as i32,
+            0,
+            -1,
+            ncurses::A_STANDOUT(),
+            0,
+        );
+        self.win.queue_for_refresh();
+    }
+}
+
 pub struct LllTextField<'a> {
     pub win: window::LllPanel,
     pub prompt: &'a str,
     pub prefix: &'a str,
     pub suffix: &'a str,
+    pub kind: PromptKind,
 }
 
 impl<'a> LllTextField<'a> {
@@ -39,6 +179,7 @@ impl<'a> LllTextField<'a> {
         prompt: &'a str,
         prefix: &'a str,
         suffix: &'a str,
+        kind: PromptKind,
     ) -> Self {
         let win = window::LllPanel::new(rows, cols, coord);
         ncurses::keypad(win.win, true);
@@ -47,6 +188,7 @@ impl<'a> LllTextField<'a> {
             prompt,
             prefix,
             suffix,
+            kind,
         }
     }
 
@@ -61,38 +203,104 @@ impl<'a> LllTextField<'a> {
         ncurses::mvwaddstr(win, 0, 0, &self.prompt);
 
         let mut line_buffer = line_buffer::LineBuffer::with_capacity(255);
-        let completer = FilenameCompleter::new();
+        let completer = LllCompleter::new(self.kind);
 
         line_buffer.insert_str(0, self.prefix);
         line_buffer.insert_str(line_buffer.len(), self.suffix);
         line_buffer.set_pos(self.prefix.as_bytes().len());
 
         let mut completion_tracker: Option<CompletionTracker> = None;
+        let mut completion_menu: Option<CompletionMenu> = None;
+        let mut history = HistoryCursor::new(self.kind);
+        // the last text killed, for yank to put back
+        let mut kill_buffer = String::new();
+
+        // long lines scroll sideways to keep the cursor in view
+        let field_width = (self.win.cols as usize).saturating_sub(coord.1).max(1);
+        let mut scroll = 0;
 
         let mut curr_pos = unicode_width::UnicodeWidthStr::width(self.prefix);
         loop {
-            ncurses::mvwaddstr(win, coord.0, coord.1 as i32, line_buffer.as_str());
+            match completion_tracker.as_ref() {
+                Some(s) => {
+                    if completion_menu.is_none() {
+                        completion_menu = CompletionMenu::new(
+                            s.candidates.len(),
+                            self.win.coords.0,
+                            self.win.cols,
+                        );
+                    }
+                    if let Some(menu) = completion_menu.as_mut() {
+                        menu.draw(s);
+                    }
+                }
+                None => completion_menu = None,
+            }
+
+            scroll = scroll_to(scroll, curr_pos, field_width);
+            let visible = visible_slice(line_buffer.as_str(), scroll, field_width);
+            ncurses::mvwaddstr(win, coord.0, coord.1 as i32, visible);
             ncurses::wclrtoeol(win);
 
             // draws cursor
             ncurses::mvwchgat(
                 win,
                 coord.0,
-                (coord.1 + curr_pos) as i32,
+                (coord.1 + curr_pos - scroll) as i32,
                 1,
                 ncurses::A_STANDOUT(),
                 0,
             );
             ncurses::wrefresh(win);
 
-            let ch = ncurses::wget_wch(win).unwrap();
-            let ch = match ch {
-                ncurses::WchResult::Char(s) => s as i32,
-                ncurses::WchResult::KeyCode(s) => s,
-            };
+            let ch = macros::wget_wch(win).unwrap();
 
             if ch == KEYMAP_T.escape {
-                return None;
+                // alt combinations come as escape followed by the key. an escape
+                // from a macro is played back on its own, as the keys after it
+                // follow without any delay
+                let alt_ch = if macros::replayed() {
+                    None
+                } else {
+                    ncurses::wtimeout(win, ALT_KEY_DELAY);
+                    let ch = macros::wget_wch(win);
+                    ncurses::wtimeout(win, -1);
+                    ch
+                };
+
+                let line = line_buffer.as_str();
+                let pos = line_buffer.pos();
+                match alt_ch {
+                    Some(ch) if ch == KEYMAP_T.word_backward => {
+                        line_buffer.set_pos(prev_word(line, pos, is_word_char));
+                        curr_pos = cursor_col(&line_buffer);
+                        completion_tracker.take();
+                        continue;
+                    }
+                    Some(ch) if ch == KEYMAP_T.word_forward => {
+                        line_buffer.set_pos(next_word(line, pos, is_word_char));
+                        curr_pos = cursor_col(&line_buffer);
+                        completion_tracker.take();
+                        continue;
+                    }
+                    // escape pressed twice is taken as one
+                    Some(ch) if ch == KEYMAP_T.escape => {}
+                    // other alt combinations are ignored, rather than leaving the
+                    // key to be run as a command once the prompt closes
+                    Some(_) => continue,
+                    None => {}
+                }
+
+                // closes the completion menu first, putting back what was typed
+                match completion_tracker.take() {
+                    Some(s) => {
+                        line_buffer.update(&s.original, s.original_pos);
+                        curr_pos = unicode_width::UnicodeWidthStr::width(
+                            &line_buffer.as_str()[..line_buffer.pos()],
+                        );
+                    }
+                    None => return None,
+                }
             } else if ch == KEYMAP_T.enter {
                 break;
             } else if ch == KEYMAP_T.home {
@@ -120,47 +328,103 @@ impl<'a> LllTextField<'a> {
                     let pos = line_buffer.pos();
                     curr_pos = unicode_width::UnicodeWidthStr::width(&line_buffer.as_str()[..pos]);
                     completion_tracker.take();
+                    history.reset();
                 }
             } else if ch == KEYMAP_T.delete {
                 if line_buffer.delete(1).is_some() {
                     completion_tracker.take();
+                    history.reset();
+                }
+            } else if ch == KEYMAP_T.line_start || ch == KEYMAP_T.line_end {
+                if ch == KEYMAP_T.line_start {
+                    line_buffer.move_home();
+                } else {
+                    line_buffer.move_end();
+                }
+                curr_pos = cursor_col(&line_buffer);
+                completion_tracker.take();
+            } else if ch == KEYMAP_T.kill_word
+                || ch == KEYMAP_T.kill_line_start
+                || ch == KEYMAP_T.kill_line_end
+            {
+                let pos = line_buffer.pos();
+                let range = if ch == KEYMAP_T.kill_word {
+                    prev_word(line_buffer.as_str(), pos, is_not_whitespace)..pos
+                } else if ch == KEYMAP_T.kill_line_start {
+                    0..pos
+                } else {
+                    pos..line_buffer.len()
+                };
+                let killed = kill_range(&mut line_buffer, range);
+                if !killed.is_empty() {
+                    kill_buffer = killed;
+                    curr_pos = cursor_col(&line_buffer);
+                    completion_tracker.take();
+                    history.reset();
+                }
+            } else if ch == KEYMAP_T.yank {
+                if !kill_buffer.is_empty() {
+                    let pos = line_buffer.pos();
+                    let mut line = String::from(line_buffer.as_str());
+                    line.insert_str(pos, &kill_buffer);
+                    line_buffer.update(&line, pos + kill_buffer.len());
+                    curr_pos = cursor_col(&line_buffer);
+                    completion_tracker.take();
+                    history.reset();
                 }
             } else if ch == KEYMAP_T.tab {
-                if completion_tracker.is_none() {
-                    let res = completer.complete_path(line_buffer.as_str(), line_buffer.pos());
-                    if let Ok((pos, mut candidates)) = res {
-                        candidates.sort_by(|x, y| {
-                            x.display()
-                                .partial_cmp(y.display())
-                                .unwrap_or(std::cmp::Ordering::Less)
-                        });
-                        let ct = CompletionTracker::new(
-                            pos,
-                            candidates,
-                            String::from(line_buffer.as_str()),
-                        );
-                        completion_tracker = Some(ct);
+                match completion_tracker.as_mut() {
+                    Some(s) => s.index = (s.index + 1) % s.candidates.len(),
+                    None => {
+                        let res = completer.complete(line_buffer.as_str(), line_buffer.pos());
+                        if let Ok((pos, mut candidates)) = res {
+                            candidates.sort_by(|x, y| {
+                                x.display()
+                                    .partial_cmp(y.display())
+                                    .unwrap_or(std::cmp::Ordering::Less)
+                            });
+                            if !candidates.is_empty() {
+                                let ct = CompletionTracker::new(
+                                    pos,
+                                    candidates,
+                                    String::from(line_buffer.as_str()),
+                                    line_buffer.pos(),
+                                );
+                                completion_tracker = Some(ct);
+                            }
+                        }
                     }
                 }
 
-                if let Some(ref mut s) = completion_tracker {
-                    if s.index < s.candidates.len() {
-                        let candidate = &s.candidates[s.index];
-                        completer.update(&mut line_buffer, s.pos, candidate.replacement());
-                        s.index += 1;
+                if let Some(ref s) = completion_tracker {
+                    let candidate = &s.candidates[s.index];
+                    completer.update(&mut line_buffer, s.pos, candidate.replacement());
+                    // a lone candidate is taken as is, the next tab completes from there
+                    if s.candidates.len() == 1 {
+                        completion_tracker = None;
                     }
                 }
                 curr_pos = unicode_width::UnicodeWidthStr::width(
                     &line_buffer.as_str()[..line_buffer.pos()],
                 );
-            } else if ch == KEYMAP_T.up {
-                unimplemented!();
-            } else if ch == KEYMAP_T.down {
-                unimplemented!();
+                history.reset();
+            } else if ch == KEYMAP_T.up || ch == KEYMAP_T.down {
+                let line = String::from(line_buffer.as_str());
+                let entry = if ch == KEYMAP_T.up {
+                    history.older(&line, line_buffer.pos())
+                } else {
+                    history.newer(&line)
+                };
+                if let Some(entry) = entry {
+                    line_buffer.update(entry, entry.len());
+                    curr_pos = unicode_width::UnicodeWidthStr::width(entry);
+                    completion_tracker.take();
+                }
             } else if let Some(ch) = std::char::from_u32(ch as u32) {
                 if line_buffer.insert(ch, 1).is_some() {
                     curr_pos += unicode_width::UnicodeWidthChar::width(ch).unwrap_or(1);
                     completion_tracker.take();
+                    history.reset();
                 }
             }
         }
@@ -169,7 +433,35 @@ impl<'a> LllTextField<'a> {
         } else {
             //            let strin = rustyline::completion::unescape(line_buffer.as_str(), ESCAPE_CHAR).into_owned();
             let strin = line_buffer.to_string();
+            // losing the history is better than losing what was typed
+            let _ = prompt_history::push(self.kind, &strin);
             Some(strin)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_motions() {
+        let line = "rename foo-bar.txt ";
+        assert_eq!(prev_word(line, line.len(), is_word_char), 15);
+        assert_eq!(prev_word(line, 15, is_word_char), 11);
+        assert_eq!(prev_word(line, line.len(), is_not_whitespace), 7);
+        assert_eq!(next_word(line, 0, is_word_char), 6);
+        assert_eq!(next_word(line, 6, is_word_char), 10);
+        assert_eq!(next_word(line, 18, is_word_char), line.len());
+    }
+
+    #[test]
+    fn scrolling() {
+        assert_eq!(scroll_to(0, 5, 10), 0);
+        assert_eq!(scroll_to(0, 12, 10), 3);
+        assert_eq!(scroll_to(3, 1, 10), 1);
+        assert_eq!(visible_slice("abcdefgh", 2, 4), "cdef");
+        assert_eq!(visible_slice("abc", 1, 10), "bc");
+        assert_eq!(visible_slice("abc", 3, 10), "");
+    }
+}
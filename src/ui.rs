@@ -1,11 +1,11 @@
-use std::fs;
-
-use users::mock::{Groups, Users};
 use users::UsersCache;
 
 use crate::config::{LllColorTheme, LllConfig};
 use crate::context::LllContext;
 use crate::fs::{LllDirEntry, LllDirList};
+use crate::status::LllStatusFormat;
+use crate::tab::LllTab;
+use crate::unix;
 use crate::window;
 
 use crate::THEME_T;
@@ -14,17 +14,61 @@ pub const ERR_COLOR: i16 = 240;
 pub const EMPTY_COLOR: i16 = 241;
 
 const MIN_WIN_WIDTH: usize = 4;
+// longest directory name shown in the tab bar
+const TAB_NAME_WIDTH: usize = 16;
+// file names are never squeezed below this to make room for details
+const MIN_NAME_WIDTH: usize = 8;
+
+pub const TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
 
 pub struct DisplayOptions {
     pub detailed: bool,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DetailColumn {
+    Size,
+    Mtime,
+    Mode,
+    Owner,
+    Group,
+}
+
+impl DetailColumn {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "size" => Some(DetailColumn::Size),
+            "mtime" => Some(DetailColumn::Mtime),
+            "mode" => Some(DetailColumn::Mode),
+            "owner" => Some(DetailColumn::Owner),
+            "group" => Some(DetailColumn::Group),
+            _ => None,
+        }
+    }
+}
+
 pub const PRIMARY_DISPLAY_OPTION: DisplayOptions = DisplayOptions { detailed: true };
+pub const SECONDARY_DISPLAY_OPTION: DisplayOptions = DisplayOptions { detailed: false };
 
 pub fn init_ncurses() {
     ncurses::setlocale(ncurses::LcCategory::all, "");
 
-    ncurses::initscr();
+    // with stdout captured (--choosefiles -), draw straight to the terminal
+    let tty = if unsafe { libc::isatty(libc::STDOUT_FILENO) } == 0 {
+        unsafe {
+            libc::fopen(
+                b"/dev/tty\0".as_ptr() as *const _,
+                b"r+\0".as_ptr() as *const _,
+            )
+        }
+    } else {
+        std::ptr::null_mut()
+    };
+    if tty.is_null() {
+        ncurses::initscr();
+    } else {
+        ncurses::newterm(None, tty, tty);
+    }
     ncurses::cbreak();
 
     ncurses::keypad(ncurses::stdscr(), true);
@@ -72,6 +116,14 @@ pub fn display_menu(win: &window::LllPanel, vals: &[String]) {
     ncurses::wnoutrefresh(win.win);
 }
 
+pub fn display_text(win: &window::LllPanel, lines: &[String]) {
+    ncurses::werase(win.win);
+    for (i, line) in lines.iter().enumerate() {
+        ncurses::mvwaddstr(win.win, i as i32, 0, line.as_str());
+    }
+    win.queue_for_refresh();
+}
+
 pub fn wprint_msg(win: &window::LllPanel, msg: &str) {
     ncurses::werase(win.win);
     ncurses::mvwaddstr(win.win, 0, 0, msg);
@@ -114,7 +166,7 @@ fn wprint_file_name(
         let ext_len = unicode_width::UnicodeWidthStr::width(extension);
         if space_avail > ext_len {
             space_avail -= ext_len;
-            ncurses::mvwaddstr(win.win, coord.0, space_avail as i32, &extension);
+            ncurses::mvwaddstr(win.win, coord.0, coord.1 + space_avail as i32, &extension);
         }
     }
     if space_avail < 2 {
@@ -164,6 +216,7 @@ fn wprint_entry_detailed(
     file: &LllDirEntry,
     prefix: (usize, &str),
     coord: (i32, i32),
+    details: &str,
 ) {
     if win.cols <= prefix.0 as i32 {
         return;
@@ -173,13 +226,74 @@ fn wprint_entry_detailed(
 
     let coord = (coord.0, coord.1 + prefix.0 as i32);
 
+    let details_len = unicode_width::UnicodeWidthStr::width(details);
+    if details_len + MIN_NAME_WIDTH + 1 > space_avail {
+        wprint_file_name(win, file.file_name(), coord, space_avail);
+        return;
+    }
+    let space_avail = space_avail - details_len - 1;
     wprint_file_name(win, file.file_name(), coord, space_avail);
+    ncurses::mvwaddstr(win.win, coord.0, win.cols - details_len as i32, details);
+}
+
+fn entry_details(entry: &mut LllDirEntry, config_t: &LllConfig, usercache: &UsersCache) -> String {
+    use std::os::unix::fs::PermissionsExt;
+
+    let columns = &config_t.detail_columns;
+    let mut details: Vec<String> = Vec::with_capacity(columns.len());
+    for column in columns {
+        match column {
+            DetailColumn::Size => {
+                let size = if entry.file_path().is_dir() {
+                    match entry.children(&config_t.sort_option) {
+                        Some(s) => s.to_string(),
+                        None => String::from("?"),
+                    }
+                } else {
+                    file_size_to_string(entry.metadata.len)
+                };
+                details.push(format!("{:>6}", size));
+            }
+            DetailColumn::Mtime => {
+                details.push(unix::format_time(&entry.metadata.modified, TIME_FORMAT));
+            }
+            DetailColumn::Mode => {
+                let mode = entry.metadata.permissions.mode();
+                details.push(unix::stringify_mode(mode));
+            }
+            DetailColumn::Owner => {
+                details.push(unix::user_name(usercache, entry.metadata.uid));
+            }
+            DetailColumn::Group => {
+                details.push(unix::group_name(usercache, entry.metadata.gid));
+            }
+        }
+    }
+    details.join(" ")
+}
+
+pub fn file_size_to_string(size: u64) -> String {
+    const FILE_UNITS: [&str; 6] = ["B", "K", "M", "G", "T", "P"];
+    const CONV_RATE: f64 = 1024.0;
+
+    let mut size = size as f64;
+    let mut index = 0;
+    while size >= CONV_RATE && index < FILE_UNITS.len() - 1 {
+        size /= CONV_RATE;
+        index += 1;
+    }
+    if index == 0 || size >= 100.0 {
+        format!("{:.0}{}", size, FILE_UNITS[index])
+    } else {
+        format!("{:.1}{}", size, FILE_UNITS[index])
+    }
 }
 
 pub fn display_contents(
     win: &window::LllPanel,
     dirlist: &mut LllDirList,
     config_t: &LllConfig,
+    usercache: &UsersCache,
     options: &DisplayOptions,
 ) {
     if win.cols < MIN_WIN_WIDTH as i32 {
@@ -193,24 +307,18 @@ pub fn display_contents(
     ncurses::werase(win.win);
     ncurses::wmove(win.win, 0, 0);
 
-    let draw_func = if options.detailed {
-        wprint_entry_detailed
-    } else {
-        wprint_entry
-    };
-
     let curr_index = dirlist.index.unwrap();
     dirlist
         .pagestate
         .update_page_state(curr_index, win.rows, dir_len, config_t.scroll_offset);
 
     let (start, end) = (dirlist.pagestate.start, dirlist.pagestate.end);
-    let dir_contents = &dirlist.contents[start..end];
+    let dir_contents = &mut dirlist.contents[start..end];
 
     ncurses::werase(win.win);
     ncurses::wmove(win.win, 0, 0);
 
-    for (i, entry) in dir_contents.iter().enumerate() {
+    for (i, entry) in dir_contents.iter_mut().enumerate() {
         let coord: (i32, i32) = (i as i32, 0);
 
         ncurses::wmove(win.win, coord.0, coord.1);
@@ -220,82 +328,106 @@ pub fn display_contents(
         } else {
             0
         };
+        let details = if options.detailed {
+            Some(entry_details(entry, config_t, usercache))
+        } else {
+            None
+        };
         let attrs = get_theme_attr(attr, entry);
 
-        draw_func(win, entry, attrs.0, coord);
+        match details {
+            Some(s) => wprint_entry_detailed(win, entry, attrs.0, coord, &s),
+            None => wprint_entry(win, entry, attrs.0, coord),
+        }
 
         ncurses::mvwchgat(win.win, coord.0, coord.1, -1, attrs.1, attrs.2);
     }
     win.queue_for_refresh();
 }
 
-pub fn wprint_file_status(win: &window::LllPanel, entry: &LllDirEntry) {
-    ncurses::waddch(win.win, ' ' as ncurses::chtype);
+pub fn wprint_file_status(
+    win: &window::LllPanel,
+    dirlist: &LllDirList,
+    status_format: &LllStatusFormat,
+    usercache: &UsersCache,
+) {
+    let status = status_format.render(dirlist, usercache);
 
-    let usercache: UsersCache = UsersCache::new();
-    match usercache.get_user_by_uid(entry.metadata.uid) {
-        Some(s) => match s.name().to_str() {
-            Some(name) => ncurses::waddstr(win.win, name),
-            None => ncurses::waddstr(win.win, "OsStr error"),
-        },
-        None => ncurses::waddstr(win.win, "unknown user"),
-    };
     ncurses::waddch(win.win, ' ' as ncurses::chtype);
-    match usercache.get_group_by_gid(entry.metadata.gid) {
-        Some(s) => match s.name().to_str() {
-            Some(name) => ncurses::waddstr(win.win, name),
-            None => ncurses::waddstr(win.win, "OsStr error"),
-        },
-        None => ncurses::waddstr(win.win, "unknown user"),
-    };
-
-    ncurses::waddstr(win.win, "  ");
-    wprint_file_info(win.win, entry);
+    ncurses::waddstr(win.win, &status);
     win.queue_for_refresh();
 }
 
-pub fn wprint_file_info(win: ncurses::WINDOW, file: &LllDirEntry) {
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-
-        let mode = file.metadata.permissions.mode();
+fn tab_name(tab: &LllTab) -> &str {
+    match tab.curr_path.file_name() {
+        Some(s) => s.to_str().unwrap_or("?"),
+        None => "/",
+    }
+}
 
-        ncurses::waddch(win, ' ' as ncurses::chtype);
+fn tab_label_width(index: usize, name: &str) -> usize {
+    let name_width = unicode_width::UnicodeWidthStr::width(name).min(TAB_NAME_WIDTH);
+    (index + 1).to_string().len() + 1 + name_width
+}
 
-        if file.file_path().is_dir() {
-            let is_link: u32 = libc::S_IFLNK as u32;
-            if mode >> 9 & is_link >> 9 == mode >> 9 {
-                if let Ok(path) = fs::read_link(&file.file_path()) {
-                    ncurses::waddstr(win, " -> ");
-                    ncurses::waddstr(win, path.to_str().unwrap());
-                }
-            }
-        }
+// columns needed to show every tab, nothing is drawn for a single tab
+pub fn tab_bar_width(context: &LllContext) -> usize {
+    if context.tabs.len() <= 1 {
+        return 0;
     }
+    context
+        .tabs
+        .iter()
+        .enumerate()
+        .map(|(i, tab)| tab_label_width(i, tab_name(tab)) + 1)
+        .sum::<usize>()
+        - 1
 }
 
 pub fn redraw_tab_view(win: &window::LllPanel, context: &LllContext) {
     let tab_len = context.tabs.len();
     ncurses::werase(win.win);
-    if tab_len == 1 {
-    } else if tab_len >= 6 {
-        ncurses::wmove(win.win, 0, 0);
-        ncurses::wattron(win.win, ncurses::A_BOLD() | ncurses::A_STANDOUT());
-        ncurses::waddstr(win.win, &format!("{}", context.curr_tab_index + 1));
-        ncurses::wattroff(win.win, ncurses::A_STANDOUT());
-        ncurses::waddstr(win.win, &format!(" {}", tab_len));
-        ncurses::wattroff(win.win, ncurses::A_BOLD());
-    } else {
+    if tab_len > 1 {
+        let widths: Vec<usize> = context
+            .tabs
+            .iter()
+            .enumerate()
+            .map(|(i, tab)| tab_label_width(i, tab_name(tab)) + 1)
+            .collect();
+
+        // scroll just far enough for the current tab to fit
+        let space_avail = win.cols as usize + 1;
+        let curr_index = context.curr_tab_index;
+        let mut start = 0;
+        while start < curr_index && widths[start..=curr_index].iter().sum::<usize>() > space_avail {
+            start += 1;
+        }
+
+        let mut x: usize = 0;
         ncurses::wattron(win.win, ncurses::A_BOLD());
-        for i in 0..tab_len {
-            if i == context.curr_tab_index {
+        for (i, (tab, width)) in context
+            .tabs
+            .iter()
+            .zip(widths.iter())
+            .enumerate()
+            .skip(start)
+        {
+            let number = format!("{} ", i + 1);
+            if x + number.len() >= win.cols as usize {
+                break;
+            }
+            if i == curr_index {
                 ncurses::wattron(win.win, ncurses::A_STANDOUT());
-                ncurses::waddstr(win.win, &format!("{} ", i + 1));
+            }
+            ncurses::mvwaddstr(win.win, 0, x as i32, &number);
+
+            let name_x = x + number.len();
+            let name_space = (win.cols as usize - name_x).min(TAB_NAME_WIDTH);
+            wprint_file_name(win, tab_name(tab), (0, name_x as i32), name_space);
+            if i == curr_index {
                 ncurses::wattroff(win.win, ncurses::A_STANDOUT());
-            } else {
-                ncurses::waddstr(win.win, &format!("{} ", i + 1));
             }
+            x += width;
         }
         ncurses::wattroff(win.win, ncurses::A_BOLD());
     }
@@ -1,5 +1,11 @@
+use std::ffi::CString;
+use std::os::unix::ffi::OsStrExt;
 use std::path::{Path, PathBuf};
 use std::process;
+use std::time;
+
+use users::mock::{Groups, Users};
+use users::UsersCache;
 
 use crate::config::mimetype;
 
@@ -48,6 +54,53 @@ pub fn stringify_mode(mode: u32) -> String {
     mode_str
 }
 
+pub fn format_time(time: &time::SystemTime, format: &str) -> String {
+    let secs = match time.duration_since(time::UNIX_EPOCH) {
+        Ok(s) => s.as_secs() as libc::time_t,
+        Err(_) => 0,
+    };
+    let format = match CString::new(format) {
+        Ok(s) => s,
+        Err(_) => return String::new(),
+    };
+
+    let mut buf: [u8; 128] = [0; 128];
+    let len = unsafe {
+        let mut tm: libc::tm = std::mem::zeroed();
+        libc::localtime_r(&secs, &mut tm);
+        libc::strftime(
+            buf.as_mut_ptr() as *mut libc::c_char,
+            buf.len(),
+            format.as_ptr(),
+            &tm,
+        )
+    };
+    String::from_utf8_lossy(&buf[..len]).into_owned()
+}
+
+pub fn user_name(usercache: &UsersCache, uid: u32) -> String {
+    match usercache.get_user_by_uid(uid) {
+        Some(s) => s.name().to_string_lossy().into_owned(),
+        None => uid.to_string(),
+    }
+}
+
+pub fn group_name(usercache: &UsersCache, gid: u32) -> String {
+    match usercache.get_group_by_gid(gid) {
+        Some(s) => s.name().to_string_lossy().into_owned(),
+        None => gid.to_string(),
+    }
+}
+
+pub fn free_space(path: &Path) -> Option<u64> {
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
 pub fn set_mode(path: &Path, mode: u32) {
     let os_path = path.as_os_str();
     if let Some(s) = os_path.to_str() {
@@ -58,6 +111,24 @@ pub fn set_mode(path: &Path, mode: u32) {
     }
 }
 
+pub fn get_mimetype(path: &Path) -> Option<String> {
+    let output = process::Command::new("file")
+        .args(&["--mime-type", "-Lb"])
+        .arg(path)
+        .stderr(process::Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let mimetype = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if mimetype.is_empty() {
+        None
+    } else {
+        Some(mimetype)
+    }
+}
+
 pub fn open_with_entry(paths: &[&PathBuf], entry: &mimetype::LllMimetypeEntry) {
     let program = entry.program.clone();
 
@@ -5,6 +5,10 @@ pub struct LllPageState {
 }
 
 impl LllPageState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
     pub fn update_page_state(
         &mut self,
         index: usize,
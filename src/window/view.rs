@@ -10,23 +10,17 @@ pub struct LllView {
     pub right_win: LllPanel,
     pub bot_win: LllPanel,
     pub win_ratio: (usize, usize, usize),
+    pub tab_width: usize,
 }
 
 impl LllView {
-    pub fn new(win_ratio: (usize, usize, usize)) -> Self {
+    pub fn new(win_ratio: (usize, usize, usize), tab_width: usize) -> Self {
         let sum_ratio: usize = win_ratio.0 + win_ratio.1 + win_ratio.2;
 
         let (term_rows, term_cols) = ui::getmaxyx();
         let term_divide: i32 = term_cols / sum_ratio as i32;
 
-        // window for tabs
-        let win_xy: (i32, i32) = (1, 10);
-        let win_coord: (usize, usize) = (0, term_cols as usize - win_xy.1 as usize);
-        let tab_win = LllPanel::new(win_xy.0, win_xy.1, win_coord);
-
-        let win_xy: (i32, i32) = (1, term_cols - tab_win.cols);
-        let win_coord: (usize, usize) = (0, 0);
-        let top_win = LllPanel::new(win_xy.0, win_xy.1, win_coord);
+        let (top_win, tab_win) = Self::top_windows(term_cols, tab_width);
 
         let offset = 0;
 
@@ -58,11 +52,37 @@ impl LllView {
             right_win,
             bot_win,
             win_ratio,
+            tab_width,
         }
     }
 
+    // the tab bar takes as much of the top line as it needs, up to half of it
+    fn top_windows(term_cols: i32, tab_width: usize) -> (LllPanel, LllPanel) {
+        let tab_cols = (tab_width as i32).min(term_cols / 2).max(1);
+
+        // window for tabs
+        let win_xy: (i32, i32) = (1, tab_cols);
+        let win_coord: (usize, usize) = (0, (term_cols - tab_cols) as usize);
+        let tab_win = LllPanel::new(win_xy.0, win_xy.1, win_coord);
+
+        let win_xy: (i32, i32) = (1, term_cols - tab_win.cols);
+        let win_coord: (usize, usize) = (0, 0);
+        let top_win = LllPanel::new(win_xy.0, win_xy.1, win_coord);
+
+        (top_win, tab_win)
+    }
+
+    pub fn resize_tab_win(&mut self, tab_width: usize) {
+        let (_, term_cols) = ui::getmaxyx();
+        let (top_win, tab_win) = Self::top_windows(term_cols, tab_width);
+
+        self.top_win = top_win;
+        self.tab_win = tab_win;
+        self.tab_width = tab_width;
+    }
+
     pub fn resize_views(&mut self) {
-        let new_view = Self::new(self.win_ratio);
+        let new_view = Self::new(self.win_ratio, self.tab_width);
 
         self.top_win = new_view.top_win;
         self.bot_win = new_view.bot_win;